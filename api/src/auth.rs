@@ -0,0 +1,273 @@
+use crate::error::ApiError;
+use crate::models::WithdrawalRequest;
+use axum::http::{HeaderMap, header::AUTHORIZATION};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::collections::HashSet;
+
+/// 签名挑战有效期(秒)
+pub const CHALLENGE_TTL_SECONDS: i64 = 300;
+
+/// 访问令牌有效期(秒)
+pub const TOKEN_TTL_SECONDS: i64 = 3600;
+
+/// 管理接口 API Key 前缀
+pub const ADMIN_KEY_PREFIX: &str = "bbk_";
+
+/// 签名登录令牌前缀
+pub const SESSION_TOKEN_PREFIX: &str = "bbs_";
+
+/// 令牌和 API Key 的 SHA-256 摘要，数据库中只保存摘要
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// 生成带前缀的随机令牌
+pub fn generate_token(prefix: &str) -> String {
+    format!(
+        "{}{}{}",
+        prefix,
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// 读取 `Authorization: Bearer <token>` 请求头
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
+
+/// 统一地址格式：小写并带 0x 前缀，不合法时返回 None
+pub fn normalize_address(address: &str) -> Option<String> {
+    let hex = address.strip_prefix("0x").unwrap_or(address);
+    (hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .then(|| format!("0x{}", hex.to_ascii_lowercase()))
+}
+
+/// 等待客户端签名的登录挑战
+#[derive(Debug, Serialize)]
+pub struct AuthChallenge {
+    pub nonce: uuid::Uuid,
+    pub address: String,
+    pub message: String, // 客户端按原文作为 personal message 签名
+    pub expires_at: DateTime<Utc>,
+}
+
+/// 验签通过后签发的访问令牌
+#[derive(Debug, Serialize)]
+pub struct AuthSession {
+    pub token: String,
+    pub address: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// 为地址生成一次性签名挑战，同时清理已过期的挑战和令牌
+pub async fn create_challenge(pool: &PgPool, address: &str) -> anyhow::Result<AuthChallenge> {
+    sqlx::query("DELETE FROM auth_challenges WHERE expires_at < NOW()")
+        .execute(pool)
+        .await?;
+    sqlx::query("DELETE FROM auth_sessions WHERE expires_at < NOW()")
+        .execute(pool)
+        .await?;
+
+    let nonce = uuid::Uuid::new_v4();
+    let expires_at = Utc::now() + Duration::seconds(CHALLENGE_TTL_SECONDS);
+    let message = format!(
+        "BuckyBank wants you to sign in with your Sui account:\n{}\n\nNonce: {}\nExpires At: {}",
+        address,
+        nonce,
+        expires_at.to_rfc3339()
+    );
+
+    sqlx::query(
+        "INSERT INTO auth_challenges (nonce, address, message, expires_at) VALUES ($1, $2, $3, $4)",
+    )
+    .bind(nonce)
+    .bind(address)
+    .bind(&message)
+    .bind(expires_at)
+    .execute(pool)
+    .await?;
+
+    Ok(AuthChallenge {
+        nonce,
+        address: address.to_string(),
+        message,
+        expires_at,
+    })
+}
+
+/// 取出未过期且未使用的挑战消息并标记为已使用，验签失败也不能再次使用
+pub async fn consume_challenge(
+    pool: &PgPool,
+    nonce: uuid::Uuid,
+    address: &str,
+) -> anyhow::Result<Option<String>> {
+    let message = sqlx::query_scalar::<_, String>(
+        r#"
+        UPDATE auth_challenges
+        SET used_at = NOW()
+        WHERE nonce = $1 AND address = $2 AND used_at IS NULL AND expires_at > NOW()
+        RETURNING message
+        "#,
+    )
+    .bind(nonce)
+    .bind(address)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(message)
+}
+
+/// 为已验签的地址签发访问令牌
+pub async fn create_session(pool: &PgPool, address: &str) -> anyhow::Result<AuthSession> {
+    let token = generate_token(SESSION_TOKEN_PREFIX);
+    let expires_at = Utc::now() + Duration::seconds(TOKEN_TTL_SECONDS);
+
+    sqlx::query("INSERT INTO auth_sessions (token_hash, address, expires_at) VALUES ($1, $2, $3)")
+        .bind(hash_token(&token))
+        .bind(address)
+        .bind(expires_at)
+        .execute(pool)
+        .await?;
+
+    Ok(AuthSession {
+        token,
+        address: address.to_string(),
+        expires_at,
+    })
+}
+
+/// 请求的查看者，决定私密字段是否可见
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Viewer {
+    Anonymous,
+    /// 通过 Sui 签名登录的地址
+    Address(String),
+    /// 持管理接口 API Key 的管理员
+    Admin(String),
+}
+
+impl Viewer {
+    /// 从 `Authorization: Bearer` 请求头解析查看者，未携带令牌时为匿名，令牌无效或过期返回 401
+    pub async fn from_headers(pool: &PgPool, headers: &HeaderMap) -> Result<Self, ApiError> {
+        match bearer_token(headers) {
+            Some(token) => Self::from_token(pool, token).await,
+            None => Ok(Viewer::Anonymous),
+        }
+    }
+
+    pub async fn from_token(pool: &PgPool, token: &str) -> Result<Self, ApiError> {
+        let token_hash = hash_token(token);
+        let viewer = if token.starts_with(ADMIN_KEY_PREFIX) {
+            sqlx::query_scalar::<_, String>(
+                "SELECT name FROM admin_api_keys WHERE key_hash = $1 AND revoked_at IS NULL",
+            )
+            .bind(&token_hash)
+            .fetch_optional(pool)
+            .await
+            .map(|name| name.map(Viewer::Admin))
+        } else {
+            sqlx::query_scalar::<_, String>(
+                "SELECT address FROM auth_sessions WHERE token_hash = $1 AND expires_at > NOW()",
+            )
+            .bind(&token_hash)
+            .fetch_optional(pool)
+            .await
+            .map(|address| address.map(Viewer::Address))
+        };
+
+        match viewer {
+            Ok(Some(viewer)) => Ok(viewer),
+            Ok(None) => Err(ApiError::unauthorized("Invalid or expired token")),
+            Err(e) => {
+                tracing::error!("Failed to authenticate token: {}", e);
+                Err(ApiError::database("Failed to authenticate token", e))
+            }
+        }
+    }
+
+    /// 查询查看者可以看到哪些存钱罐的私密字段：管理员全部可见，登录地址只能看到自己是家长或孩子的存钱罐
+    pub async fn bank_access<'a>(
+        &self,
+        pool: &PgPool,
+        bucky_bank_ids: impl IntoIterator<Item = &'a str>,
+    ) -> Result<BankAccess, ApiError> {
+        let address = match self {
+            Viewer::Admin(_) => return Ok(BankAccess::All),
+            Viewer::Anonymous => return Ok(BankAccess::Banks(HashSet::new())),
+            Viewer::Address(address) => address,
+        };
+
+        let ids: Vec<&str> = bucky_bank_ids.into_iter().collect();
+        if ids.is_empty() {
+            return Ok(BankAccess::Banks(HashSet::new()));
+        }
+
+        let visible = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT bucky_bank_id FROM bucky_bank_created_events
+            WHERE bucky_bank_id = ANY($1) AND (parent_address = $2 OR child_address = $2)
+            "#,
+        )
+        .bind(&ids)
+        .bind(address)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to check bank access: {}", e);
+            ApiError::database("Failed to check bank access", e)
+        })?;
+
+        Ok(BankAccess::Banks(visible.into_iter().collect()))
+    }
+
+    /// 不查库判断：管理员或地址在给定的参与方中
+    pub fn is_party(&self, addresses: &[String]) -> bool {
+        match self {
+            Viewer::Admin(_) => true,
+            Viewer::Address(address) => addresses.contains(address),
+            Viewer::Anonymous => false,
+        }
+    }
+}
+
+/// 查看者可见私密字段的存钱罐范围
+#[derive(Debug)]
+pub enum BankAccess {
+    All,
+    Banks(HashSet<String>),
+}
+
+impl BankAccess {
+    pub fn allows(&self, bucky_bank_id: &str) -> bool {
+        match self {
+            BankAccess::All => true,
+            BankAccess::Banks(banks) => banks.contains(bucky_bank_id),
+        }
+    }
+}
+
+/// 隐藏查看者无权查看的提取原因
+pub async fn redact_withdrawal_reasons(
+    pool: &PgPool,
+    viewer: &Viewer,
+    requests: &mut [WithdrawalRequest],
+) -> Result<(), ApiError> {
+    let access = viewer
+        .bank_access(pool, requests.iter().map(|r| r.bucky_bank_id.as_str()))
+        .await?;
+    for request in requests.iter_mut() {
+        if !access.allows(&request.bucky_bank_id) {
+            request.reason = None;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,401 @@
+use crate::models::{
+    BalancePoint, BankReward, BuckyBank, BuckyBankQuery, Deposit, PlatformStats, PortfolioBank,
+    RewardClaim, RewardQuery, Withdrawal, WithdrawalRequest, WithdrawalRequestQuery,
+};
+use crate::pagination::{CursorId, Keyset, Page, PageRequest};
+use anyhow::Result;
+use sqlx::postgres::PgRow;
+use sqlx::{FromRow, PgPool, Postgres, QueryBuilder};
+
+// BuckyBank查询列，can_withdraw 按状态和截止时间计算
+const BUCKY_BANK_COLUMNS: &str = "*, (status = 'Completed' AND deadline_ms <= (EXTRACT(EPOCH FROM NOW()) * 1000)::BIGINT) AS can_withdraw";
+
+/// 列表排序键：按时间列和主键倒序
+struct SortKey {
+    column: &'static str,
+}
+
+const BY_CREATED_AT: SortKey = SortKey {
+    column: "created_at_ms",
+};
+
+const BY_TIMESTAMP: SortKey = SortKey {
+    column: "timestamp_ms",
+};
+
+/// `/api/v1` 使用的只读查询
+pub struct ApiDatabase {
+    pool: PgPool,
+}
+
+impl ApiDatabase {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub fn pool(&self) -> &PgPool {
+        &self.pool
+    }
+
+    pub async fn get_bucky_banks(
+        &self,
+        query: &BuckyBankQuery,
+        page: &PageRequest,
+    ) -> Result<Page<BuckyBank>> {
+        self.fetch_page(
+            BUCKY_BANK_COLUMNS,
+            "bucky_bank_created_events",
+            &[
+                ("parent_address", query.parent_address.as_deref()),
+                ("child_address", query.child_address.as_deref()),
+                ("status", query.status.as_deref()),
+            ],
+            BY_CREATED_AT,
+            page,
+        )
+        .await
+    }
+
+    pub async fn get_bucky_bank_by_id(&self, bucky_bank_id: &str) -> Result<Option<BuckyBank>> {
+        let result = sqlx::query_as::<_, BuckyBank>(&format!(
+            "SELECT {} FROM bucky_bank_created_events WHERE bucky_bank_id = $1",
+            BUCKY_BANK_COLUMNS
+        ))
+        .bind(bucky_bank_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    pub async fn get_deposits(
+        &self,
+        bucky_bank_id: &str,
+        page: &PageRequest,
+    ) -> Result<Page<Deposit>> {
+        self.fetch_page(
+            "*",
+            "deposit_made_events",
+            &[("bucky_bank_id", Some(bucky_bank_id))],
+            BY_CREATED_AT,
+            page,
+        )
+        .await
+    }
+
+    pub async fn get_withdrawals(
+        &self,
+        bucky_bank_id: &str,
+        page: &PageRequest,
+    ) -> Result<Page<Withdrawal>> {
+        self.fetch_page(
+            "*",
+            "withdrawed_events",
+            &[("bucky_bank_id", Some(bucky_bank_id))],
+            BY_CREATED_AT,
+            page,
+        )
+        .await
+    }
+
+    pub async fn get_withdrawal_requests(
+        &self,
+        query: &WithdrawalRequestQuery,
+        page: &PageRequest,
+    ) -> Result<Page<WithdrawalRequest>> {
+        self.fetch_page(
+            "*",
+            "withdrawal_requests",
+            &[
+                ("bucky_bank_id", query.bucky_bank_id.as_deref()),
+                ("requester", query.requester.as_deref()),
+                ("status", query.status.as_deref()),
+            ],
+            BY_CREATED_AT,
+            page,
+        )
+        .await
+    }
+
+    pub async fn get_withdrawal_request_by_id(
+        &self,
+        request_id: &str,
+    ) -> Result<Option<WithdrawalRequest>> {
+        let result = sqlx::query_as::<_, WithdrawalRequest>(
+            "SELECT * FROM withdrawal_requests WHERE request_id = $1",
+        )
+        .bind(request_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    pub async fn get_bank_rewards(
+        &self,
+        query: &RewardQuery,
+        page: &PageRequest,
+    ) -> Result<Page<BankReward>> {
+        self.fetch_page(
+            "*",
+            "bank_reward_events",
+            &[
+                ("bucky_bank_id", query.bucky_bank_id.as_deref()),
+                ("reward_receiver", query.reward_receiver.as_deref()),
+            ],
+            BY_TIMESTAMP,
+            page,
+        )
+        .await
+    }
+
+    pub async fn get_reward_claims(
+        &self,
+        query: &RewardQuery,
+        page: &PageRequest,
+    ) -> Result<Page<RewardClaim>> {
+        self.fetch_page(
+            "*",
+            "reward_claim_events",
+            &[
+                ("bucky_bank_id", query.bucky_bank_id.as_deref()),
+                ("reward_receiver", query.reward_receiver.as_deref()),
+            ],
+            BY_CREATED_AT,
+            page,
+        )
+        .await
+    }
+
+    pub async fn get_platform_stats(&self) -> Result<Option<PlatformStats>> {
+        let result = sqlx::query_as::<_, PlatformStats>(
+            r#"
+            SELECT
+                total_bucky_banks, active_bucky_banks, completed_bucky_banks, total_balance,
+                total_deposits, total_deposit_amount, unique_depositors,
+                total_withdrawals, total_withdrawal_amount,
+                total_rewards_distributed, total_rewards_claimed, refreshed_at
+            FROM platform_stats
+            "#,
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// 查询地址作为家长或孩子的全部 BuckyBank，并在同一条 SQL 中聚合存取款、待审批提取和未领取奖励
+    pub async fn get_portfolio(&self, address: &str) -> Result<Vec<PortfolioBank>> {
+        let banks = sqlx::query_as::<_, PortfolioBank>(&format!(
+            r#"
+            WITH banks AS (
+                SELECT {}
+                FROM bucky_bank_created_events
+                WHERE parent_address = $1 OR child_address = $1
+            ),
+            deposits AS (
+                SELECT bucky_bank_id, SUM(amount) AS amount
+                FROM deposit_made_events
+                WHERE bucky_bank_id IN (SELECT bucky_bank_id FROM banks)
+                GROUP BY bucky_bank_id
+            ),
+            withdrawals AS (
+                SELECT bucky_bank_id, SUM(amount) AS amount
+                FROM withdrawed_events
+                WHERE bucky_bank_id IN (SELECT bucky_bank_id FROM banks)
+                GROUP BY bucky_bank_id
+            ),
+            pending AS (
+                SELECT bucky_bank_id, COUNT(*) AS count, SUM(amount) AS amount
+                FROM withdrawal_requests
+                WHERE status = 'Pending' AND bucky_bank_id IN (SELECT bucky_bank_id FROM banks)
+                GROUP BY bucky_bank_id
+            ),
+            rewards AS (
+                SELECT bucky_bank_id, SUM(bank_reward) AS amount
+                FROM bank_reward_events
+                WHERE bucky_bank_id IN (SELECT bucky_bank_id FROM banks)
+                GROUP BY bucky_bank_id
+            ),
+            claims AS (
+                SELECT bucky_bank_id, SUM(reward_amount) AS amount
+                FROM reward_claim_events
+                WHERE bucky_bank_id IN (SELECT bucky_bank_id FROM banks)
+                GROUP BY bucky_bank_id
+            )
+            SELECT
+                b.*,
+                b.parent_address = $1 AS is_parent,
+                b.child_address = $1 AS is_child,
+                COALESCE(d.amount, 0)::BIGINT AS total_deposited,
+                COALESCE(w.amount, 0)::BIGINT AS total_withdrawn,
+                CASE WHEN b.target_amount > 0
+                    THEN LEAST(b.current_balance * 100.0 / b.target_amount, 100)::DOUBLE PRECISION
+                    ELSE 0
+                END AS progress_percent,
+                COALESCE(p.count, 0) AS pending_withdrawal_count,
+                COALESCE(p.amount, 0)::BIGINT AS pending_withdrawal_amount,
+                GREATEST(COALESCE(r.amount, 0) - COALESCE(c.amount, 0), 0)::BIGINT AS unclaimed_rewards
+            FROM banks b
+            LEFT JOIN deposits d ON d.bucky_bank_id = b.bucky_bank_id
+            LEFT JOIN withdrawals w ON w.bucky_bank_id = b.bucky_bank_id
+            LEFT JOIN pending p ON p.bucky_bank_id = b.bucky_bank_id
+            LEFT JOIN rewards r ON r.bucky_bank_id = b.bucky_bank_id
+            LEFT JOIN claims c ON c.bucky_bank_id = b.bucky_bank_id
+            ORDER BY b.created_at_ms DESC, b.id DESC
+            "#,
+            BUCKY_BANK_COLUMNS
+        ))
+        .bind(address)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(banks)
+    }
+
+    /// 按 day / week 聚合存取款流水，返回每个区间末的累计余额，没有流水的区间沿用上一区间的余额
+    ///
+    /// 序列一直延续到 `now_ms` 所在的区间，最后一笔流水之后的区间同样沿用最后的余额。
+    pub async fn get_balance_timeseries(
+        &self,
+        bucky_bank_id: &str,
+        bucket: &str,
+        target_amount: i64,
+        now_ms: i64,
+    ) -> Result<Vec<BalancePoint>> {
+        let points = sqlx::query_as::<_, BalancePoint>(
+            r#"
+            WITH ledger AS (
+                SELECT created_at_ms, amount AS deposited, 0::BIGINT AS withdrawn
+                FROM deposit_made_events
+                WHERE bucky_bank_id = $1
+                UNION ALL
+                SELECT created_at_ms, 0::BIGINT AS deposited, amount AS withdrawn
+                FROM withdrawed_events
+                WHERE bucky_bank_id = $1
+            ),
+            buckets AS (
+                SELECT
+                    date_trunc($2, to_timestamp(created_at_ms / 1000.0) AT TIME ZONE 'UTC') AS bucket_start,
+                    SUM(deposited) AS deposited,
+                    SUM(withdrawn) AS withdrawn
+                FROM ledger
+                GROUP BY 1
+            ),
+            series AS (
+                SELECT generate_series(
+                    MIN(bucket_start),
+                    GREATEST(MAX(bucket_start), date_trunc($2, to_timestamp($4 / 1000.0) AT TIME ZONE 'UTC')),
+                    ('1 ' || $2)::INTERVAL
+                ) AS bucket_start
+                FROM buckets
+            ),
+            filled AS (
+                SELECT
+                    s.bucket_start,
+                    COALESCE(b.deposited, 0) AS deposited,
+                    COALESCE(b.withdrawn, 0) AS withdrawn,
+                    SUM(COALESCE(b.deposited, 0) - COALESCE(b.withdrawn, 0)) OVER (ORDER BY s.bucket_start) AS balance
+                FROM series s
+                LEFT JOIN buckets b ON b.bucket_start = s.bucket_start
+            )
+            SELECT
+                (EXTRACT(EPOCH FROM bucket_start) * 1000)::BIGINT AS bucket_start_ms,
+                deposited::BIGINT AS deposited,
+                withdrawn::BIGINT AS withdrawn,
+                balance::BIGINT AS balance,
+                CASE WHEN $3 > 0
+                    THEN LEAST(balance * 100.0 / $3, 100)::DOUBLE PRECISION
+                    ELSE 0
+                END AS percent_of_target
+            FROM filled
+            ORDER BY bucket_start
+            "#,
+        )
+        .bind(bucky_bank_id)
+        .bind(bucket)
+        .bind(target_amount)
+        .bind(now_ms)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(points)
+    }
+
+    // 按等值条件分页查询，值为空的条件会被忽略；多取一条用于判断是否还有下一页
+    async fn fetch_page<T>(
+        &self,
+        columns: &str,
+        table: &str,
+        filters: &[(&str, Option<&str>)],
+        sort: SortKey,
+        page: &PageRequest,
+    ) -> Result<Page<T>>
+    where
+        T: for<'r> FromRow<'r, PgRow> + Keyset + Send + Unpin,
+    {
+        // 总数需要全表计数，只在客户端要求时查询
+        let total = if page.include_total {
+            let mut count_query =
+                QueryBuilder::<Postgres>::new(format!("SELECT COUNT(*) FROM {}", table));
+            Self::push_filters(&mut count_query, filters);
+            Some(count_query.build_query_scalar().fetch_one(&self.pool).await?)
+        } else {
+            None
+        };
+
+        let mut data_query =
+            QueryBuilder::<Postgres>::new(format!("SELECT {} FROM {}", columns, table));
+        let has_filters = Self::push_filters(&mut data_query, filters);
+        if let Some(after) = &page.after {
+            data_query
+                .push(if has_filters { " AND (" } else { " WHERE (" })
+                .push(sort.column)
+                .push(", id) < (")
+                .push_bind(after.sort_key)
+                .push(", ");
+            // 主键类型在解析游标时已按记录类型校验
+            match after.id {
+                CursorId::Uuid(id) => data_query.push_bind(id),
+                CursorId::Integer(id) => data_query.push_bind(id),
+            };
+            data_query.push(")");
+        }
+        data_query
+            .push(format!(" ORDER BY {} DESC, id DESC LIMIT ", sort.column))
+            .push_bind(page.limit + 1);
+        if page.after.is_none() {
+            data_query.push(" OFFSET ").push_bind(page.offset);
+        }
+        let mut items = data_query.build_query_as::<T>().fetch_all(&self.pool).await?;
+
+        let next_cursor = if items.len() as i64 > page.limit {
+            items.truncate(page.limit as usize);
+            items.last().map(|item| item.page_cursor().encode())
+        } else {
+            None
+        };
+
+        Ok(Page {
+            items,
+            next_cursor,
+            total,
+        })
+    }
+
+    // 返回是否添加了过滤条件
+    fn push_filters<'a>(
+        builder: &mut QueryBuilder<'a, Postgres>,
+        filters: &[(&str, Option<&'a str>)],
+    ) -> bool {
+        let mut first = true;
+        for (column, value) in filters {
+            if let Some(value) = value {
+                builder.push(if first { " WHERE " } else { " AND " });
+                builder.push(*column).push(" = ").push_bind(*value);
+                first = false;
+            }
+        }
+        !first
+    }
+}
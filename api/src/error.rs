@@ -0,0 +1,158 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
+use serde_json::Value;
+
+/// 接口错误，按类型映射到对应的 HTTP 状态码
+///
+/// 错误日志由调用方记录，这里只负责生成响应，500 错误的内部原因不会返回给客户端。
+#[derive(Debug)]
+pub enum ApiError {
+    /// 资源不存在，返回 404
+    NotFound(String),
+    /// 未认证或凭证无效，返回 401
+    Unauthorized(String),
+    /// 与资源当前状态冲突，返回 409
+    Conflict(String),
+    /// 请求参数校验失败，返回 400
+    Validation {
+        message: String,
+        details: Option<Value>,
+    },
+    /// 数据库操作失败，返回 500
+    Database {
+        message: String,
+        source: anyhow::Error,
+    },
+    /// 其他内部错误（如链上 RPC 调用失败），返回 500
+    Internal {
+        message: String,
+        source: anyhow::Error,
+    },
+}
+
+/// 错误响应体
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    pub code: &'static str,
+    pub message: String,
+    pub details: Option<Value>,
+}
+
+impl ApiError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        ApiError::NotFound(message.into())
+    }
+
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        ApiError::Unauthorized(message.into())
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        ApiError::Conflict(message.into())
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        ApiError::Validation {
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn validation_with_details(message: impl Into<String>, details: Value) -> Self {
+        ApiError::Validation {
+            message: message.into(),
+            details: Some(details),
+        }
+    }
+
+    pub fn database(message: impl Into<String>, source: impl Into<anyhow::Error>) -> Self {
+        ApiError::Database {
+            message: message.into(),
+            source: source.into(),
+        }
+    }
+
+    pub fn internal(message: impl Into<String>, source: impl Into<anyhow::Error>) -> Self {
+        ApiError::Internal {
+            message: message.into(),
+            source: source.into(),
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Validation { .. } => StatusCode::BAD_REQUEST,
+            ApiError::Database { .. } | ApiError::Internal { .. } => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::NotFound(_) => "not_found",
+            ApiError::Unauthorized(_) => "unauthorized",
+            ApiError::Conflict(_) => "conflict",
+            ApiError::Validation { .. } => "validation_error",
+            ApiError::Database { .. } => "database_error",
+            ApiError::Internal { .. } => "internal_error",
+        }
+    }
+
+    /// 校验状态过滤条件，非法取值返回 400 并在 details 中列出可选值
+    pub fn check_status(status: Option<&str>, allowed: &[&str]) -> Result<(), ApiError> {
+        match status {
+            Some(status) if !allowed.contains(&status) => Err(ApiError::validation_with_details(
+                format!("Invalid status: {}", status),
+                serde_json::json!({ "field": "status", "allowed": allowed }),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiError::NotFound(message)
+            | ApiError::Unauthorized(message)
+            | ApiError::Conflict(message)
+            | ApiError::Validation { message, .. } => write!(f, "{}", message),
+            ApiError::Database { message, source } | ApiError::Internal { message, source } => {
+                write!(f, "{}: {}", message, source)
+            }
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let code = self.code();
+        let (message, details) = match self {
+            ApiError::NotFound(message)
+            | ApiError::Unauthorized(message)
+            | ApiError::Conflict(message) => (message, None),
+            ApiError::Validation { message, details } => (message, details),
+            ApiError::Database { message, .. } | ApiError::Internal { message, .. } => {
+                (message, None)
+            }
+        };
+
+        (
+            status,
+            Json(ErrorBody {
+                code,
+                message,
+                details,
+            }),
+        )
+            .into_response()
+    }
+}
@@ -0,0 +1,250 @@
+use crate::auth::{Viewer, redact_withdrawal_reasons};
+use crate::database::ApiDatabase;
+use crate::error::ApiError;
+use crate::models::{
+    BUCKY_BANK_STATUSES, BalanceTimeseries, BankReward, BuckyBank, BuckyBankQuery, Deposit,
+    PageParams, PlatformStats, Portfolio, RewardClaim, RewardQuery, TIMESERIES_BUCKETS,
+    TimeseriesQuery, WITHDRAWAL_STATUSES, Withdrawal, WithdrawalRequest, WithdrawalRequestQuery,
+};
+use crate::pagination::PageRequest;
+use crate::response::ApiResponse;
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    response::Json,
+};
+use std::sync::Arc;
+
+type ApiResult<T> = Result<Json<ApiResponse<T>>, ApiError>;
+
+// 数据库错误记录日志后返回 500
+fn database_error(context: &str, e: anyhow::Error) -> ApiError {
+    tracing::error!("{}: {}", context, e);
+    ApiError::database(context, e)
+}
+
+// 获取BuckyBank列表，支持按家长地址、孩子地址和状态过滤
+pub async fn get_bucky_banks(
+    State(db): State<Arc<ApiDatabase>>,
+    Query(query): Query<BuckyBankQuery>,
+) -> ApiResult<Vec<BuckyBank>> {
+    ApiError::check_status(query.status.as_deref(), &BUCKY_BANK_STATUSES)?;
+
+    let page = PageRequest::new::<BuckyBank>(
+        query.page,
+        query.limit,
+        query.cursor.as_deref(),
+        query.include_total.unwrap_or(false),
+    )?;
+    let banks = db
+        .get_bucky_banks(&query, &page)
+        .await
+        .map_err(|e| database_error("Failed to fetch bucky banks", e))?;
+    Ok(Json(ApiResponse::page(banks)))
+}
+
+// 根据bucky_bank_id获取单个BuckyBank
+pub async fn get_bucky_bank_by_id(
+    State(db): State<Arc<ApiDatabase>>,
+    Path(bucky_bank_id): Path<String>,
+) -> ApiResult<BuckyBank> {
+    match db.get_bucky_bank_by_id(&bucky_bank_id).await {
+        Ok(Some(bank)) => Ok(Json(ApiResponse::success(bank))),
+        Ok(None) => Err(ApiError::not_found(format!("BuckyBank {} not found", bucky_bank_id))),
+        Err(e) => Err(database_error("Failed to fetch bucky bank", e)),
+    }
+}
+
+// 获取BuckyBank的累计余额时间序列，按 ?bucket=day|week 聚合
+pub async fn get_bucky_bank_timeseries(
+    State(db): State<Arc<ApiDatabase>>,
+    Path(bucky_bank_id): Path<String>,
+    Query(query): Query<TimeseriesQuery>,
+) -> ApiResult<BalanceTimeseries> {
+    let bucket = query.bucket.unwrap_or_else(|| "day".to_string());
+    if !TIMESERIES_BUCKETS.contains(&bucket.as_str()) {
+        return Err(ApiError::validation_with_details(
+            format!("Invalid bucket: {}", bucket),
+            serde_json::json!({ "field": "bucket", "allowed": TIMESERIES_BUCKETS }),
+        ));
+    }
+
+    let bank = match db.get_bucky_bank_by_id(&bucky_bank_id).await {
+        Ok(Some(bank)) => bank,
+        Ok(None) => return Err(ApiError::not_found(format!("BuckyBank {} not found", bucky_bank_id))),
+        Err(e) => return Err(database_error("Failed to fetch bucky bank", e)),
+    };
+
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let points = db
+        .get_balance_timeseries(&bucky_bank_id, &bucket, bank.target_amount, now_ms)
+        .await
+        .map_err(|e| database_error("Failed to fetch balance timeseries", e))?;
+    Ok(Json(ApiResponse::success(BalanceTimeseries::new(&bank, bucket, points, now_ms))))
+}
+
+// 获取BuckyBank的存款记录
+pub async fn get_deposits_by_bucky_bank_id(
+    State(db): State<Arc<ApiDatabase>>,
+    Path(bucky_bank_id): Path<String>,
+    Query(params): Query<PageParams>,
+) -> ApiResult<Vec<Deposit>> {
+    let page = PageRequest::new::<Deposit>(
+        params.page,
+        params.limit,
+        params.cursor.as_deref(),
+        params.include_total.unwrap_or(false),
+    )?;
+    let deposits = db
+        .get_deposits(&bucky_bank_id, &page)
+        .await
+        .map_err(|e| database_error("Failed to fetch deposits", e))?;
+    Ok(Json(ApiResponse::page(deposits)))
+}
+
+// 获取BuckyBank的提取记录
+pub async fn get_withdrawals_by_bucky_bank_id(
+    State(db): State<Arc<ApiDatabase>>,
+    Path(bucky_bank_id): Path<String>,
+    Query(params): Query<PageParams>,
+) -> ApiResult<Vec<Withdrawal>> {
+    let page = PageRequest::new::<Withdrawal>(
+        params.page,
+        params.limit,
+        params.cursor.as_deref(),
+        params.include_total.unwrap_or(false),
+    )?;
+    let withdrawals = db
+        .get_withdrawals(&bucky_bank_id, &page)
+        .await
+        .map_err(|e| database_error("Failed to fetch withdrawals", e))?;
+    Ok(Json(ApiResponse::page(withdrawals)))
+}
+
+// 获取BuckyBank的提取请求
+pub async fn get_withdrawal_requests_by_bucky_bank_id(
+    State(db): State<Arc<ApiDatabase>>,
+    Path(bucky_bank_id): Path<String>,
+    headers: HeaderMap,
+    Query(mut query): Query<WithdrawalRequestQuery>,
+) -> ApiResult<Vec<WithdrawalRequest>> {
+    query.bucky_bank_id = Some(bucky_bank_id);
+    get_withdrawal_requests(State(db), headers, Query(query)).await
+}
+
+// 获取提取请求列表，支持按BuckyBank、申请人和状态过滤；提取原因只返回给存钱罐的家长、孩子或管理员
+pub async fn get_withdrawal_requests(
+    State(db): State<Arc<ApiDatabase>>,
+    headers: HeaderMap,
+    Query(query): Query<WithdrawalRequestQuery>,
+) -> ApiResult<Vec<WithdrawalRequest>> {
+    ApiError::check_status(query.status.as_deref(), &WITHDRAWAL_STATUSES)?;
+    let viewer = Viewer::from_headers(db.pool(), &headers).await?;
+
+    let page = PageRequest::new::<WithdrawalRequest>(
+        query.page,
+        query.limit,
+        query.cursor.as_deref(),
+        query.include_total.unwrap_or(false),
+    )?;
+    let mut requests = db
+        .get_withdrawal_requests(&query, &page)
+        .await
+        .map_err(|e| database_error("Failed to fetch withdrawal requests", e))?;
+    redact_withdrawal_reasons(db.pool(), &viewer, &mut requests.items).await?;
+    Ok(Json(ApiResponse::page(requests)))
+}
+
+// 根据request_id获取提取请求
+pub async fn get_withdrawal_request_by_id(
+    State(db): State<Arc<ApiDatabase>>,
+    Path(request_id): Path<String>,
+    headers: HeaderMap,
+) -> ApiResult<WithdrawalRequest> {
+    let viewer = Viewer::from_headers(db.pool(), &headers).await?;
+    match db.get_withdrawal_request_by_id(&request_id).await {
+        Ok(Some(mut request)) => {
+            redact_withdrawal_reasons(db.pool(), &viewer, std::slice::from_mut(&mut request)).await?;
+            Ok(Json(ApiResponse::success(request)))
+        }
+        Ok(None) => Err(ApiError::not_found(format!("Withdrawal request {} not found", request_id))),
+        Err(e) => Err(database_error("Failed to fetch withdrawal request", e)),
+    }
+}
+
+// 获取BuckyBank的奖励分配记录
+pub async fn get_bank_rewards_by_bucky_bank_id(
+    State(db): State<Arc<ApiDatabase>>,
+    Path(bucky_bank_id): Path<String>,
+    Query(mut query): Query<RewardQuery>,
+) -> ApiResult<Vec<BankReward>> {
+    query.bucky_bank_id = Some(bucky_bank_id);
+    get_bank_rewards(State(db), Query(query)).await
+}
+
+// 获取奖励分配记录，支持按BuckyBank和奖励接收者过滤
+pub async fn get_bank_rewards(
+    State(db): State<Arc<ApiDatabase>>,
+    Query(query): Query<RewardQuery>,
+) -> ApiResult<Vec<BankReward>> {
+    let page = PageRequest::new::<BankReward>(
+        query.page,
+        query.limit,
+        query.cursor.as_deref(),
+        query.include_total.unwrap_or(false),
+    )?;
+    let rewards = db
+        .get_bank_rewards(&query, &page)
+        .await
+        .map_err(|e| database_error("Failed to fetch bank rewards", e))?;
+    Ok(Json(ApiResponse::page(rewards)))
+}
+
+// 获取BuckyBank的奖励领取记录
+pub async fn get_reward_claims_by_bucky_bank_id(
+    State(db): State<Arc<ApiDatabase>>,
+    Path(bucky_bank_id): Path<String>,
+    Query(mut query): Query<RewardQuery>,
+) -> ApiResult<Vec<RewardClaim>> {
+    query.bucky_bank_id = Some(bucky_bank_id);
+    get_reward_claims(State(db), Query(query)).await
+}
+
+// 获取奖励领取记录，支持按BuckyBank和奖励接收者过滤
+pub async fn get_reward_claims(
+    State(db): State<Arc<ApiDatabase>>,
+    Query(query): Query<RewardQuery>,
+) -> ApiResult<Vec<RewardClaim>> {
+    let page = PageRequest::new::<RewardClaim>(
+        query.page,
+        query.limit,
+        query.cursor.as_deref(),
+        query.include_total.unwrap_or(false),
+    )?;
+    let claims = db
+        .get_reward_claims(&query, &page)
+        .await
+        .map_err(|e| database_error("Failed to fetch reward claims", e))?;
+    Ok(Json(ApiResponse::page(claims)))
+}
+
+// 获取地址名下的全部BuckyBank及汇总，地址可以是家长或孩子
+pub async fn get_address_portfolio(
+    State(db): State<Arc<ApiDatabase>>,
+    Path(address): Path<String>,
+) -> ApiResult<Portfolio> {
+    let banks = db
+        .get_portfolio(&address)
+        .await
+        .map_err(|e| database_error("Failed to fetch portfolio", e))?;
+    Ok(Json(ApiResponse::success(Portfolio::new(address, banks))))
+}
+
+// 获取平台统计
+pub async fn get_platform_stats(State(db): State<Arc<ApiDatabase>>) -> ApiResult<PlatformStats> {
+    match db.get_platform_stats().await {
+        Ok(Some(stats)) => Ok(Json(ApiResponse::success(stats))),
+        Ok(None) => Err(ApiError::not_found("Platform stats have not been computed yet")),
+        Err(e) => Err(database_error("Failed to fetch platform stats", e)),
+    }
+}
@@ -0,0 +1,71 @@
+//! BuckyBank 对外查询 API
+//!
+//! 后端和索引器共用同一套 `/api/v1` 路由、数据模型和响应格式，
+//! 两个服务只需要把 [`router`] 合并到各自的 axum Router 中。
+
+pub mod auth;
+pub mod database;
+pub mod error;
+pub mod handlers;
+pub mod models;
+pub mod pagination;
+pub mod response;
+pub mod stream;
+
+use axum::{Router, routing::get};
+use sqlx::PgPool;
+use std::sync::Arc;
+
+pub use auth::Viewer;
+pub use database::ApiDatabase;
+pub use error::ApiError;
+pub use response::ApiResponse;
+pub use stream::{EVENT_CHANNEL, EventNotification};
+
+/// API 版本前缀
+pub const API_PREFIX: &str = "/api/v1";
+
+/// 创建 `/api/v1` 路由，返回的 Router 已绑定状态，可直接 merge 到任意服务中
+///
+/// 需要在 tokio 运行时中调用，会启动监听 [`EVENT_CHANNEL`] 的后台任务供实时推送使用。
+pub fn router(pool: PgPool) -> Router {
+    let events = stream::EventStream::spawn(pool.clone());
+    let db = Arc::new(ApiDatabase::new(pool));
+
+    // 实时推送：SSE 和 WebSocket，均支持 ?bucky_bank_id= 和 ?address= 过滤
+    let realtime = Router::new()
+        .route("/stream", get(stream::stream_events))
+        .route("/ws", get(stream::websocket_events))
+        .with_state(events);
+
+    let v1 = Router::new()
+        // BuckyBank 相关API
+        .route("/bucky-banks", get(handlers::get_bucky_banks))
+        .route("/bucky-banks/:id", get(handlers::get_bucky_bank_by_id))
+        .route("/bucky-banks/:id/timeseries", get(handlers::get_bucky_bank_timeseries))
+        .route("/bucky-banks/:id/deposits", get(handlers::get_deposits_by_bucky_bank_id))
+        .route("/bucky-banks/:id/withdrawals", get(handlers::get_withdrawals_by_bucky_bank_id))
+        .route(
+            "/bucky-banks/:id/withdrawal-requests",
+            get(handlers::get_withdrawal_requests_by_bucky_bank_id),
+        )
+        .route("/bucky-banks/:id/rewards", get(handlers::get_bank_rewards_by_bucky_bank_id))
+        .route(
+            "/bucky-banks/:id/reward-claims",
+            get(handlers::get_reward_claims_by_bucky_bank_id),
+        )
+        // 提取请求相关API，按申请人查询使用 ?requester= 过滤
+        .route("/withdrawal-requests", get(handlers::get_withdrawal_requests))
+        .route("/withdrawal-requests/:request_id", get(handlers::get_withdrawal_request_by_id))
+        // 奖励相关API，按接收者查询使用 ?reward_receiver= 过滤
+        .route("/rewards", get(handlers::get_bank_rewards))
+        .route("/reward-claims", get(handlers::get_reward_claims))
+        // 平台统计API
+        .route("/stats", get(handlers::get_platform_stats))
+        // 地址汇总API
+        .route("/addresses/:address/portfolio", get(handlers::get_address_portfolio))
+        .with_state(db)
+        .merge(realtime);
+
+    Router::new().nest(API_PREFIX, v1)
+}
@@ -0,0 +1,334 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::pagination::{Keyset, PageCursor};
+
+// 存钱罐状态，与合约中的 Status 枚举一致
+pub const BUCKY_BANK_STATUSES: [&str; 4] = ["Active", "Frozen", "Completed", "Closed"];
+
+// 提取请求状态，与合约中的 WithdrawalStatus 枚举一致
+pub const WITHDRAWAL_STATUSES: [&str; 5] = ["Pending", "Approved", "Rejected", "Cancelled", "Withdrawed"];
+
+// 余额时间序列支持的聚合粒度，取值即 Postgres date_trunc 的精度
+pub const TIMESERIES_BUCKETS: [&str; 2] = ["day", "week"];
+
+const MS_PER_DAY: f64 = 86_400_000.0;
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct BuckyBank {
+    pub id: Uuid,
+    pub bucky_bank_id: String,
+    pub name: String,
+    pub parent_address: String,
+    pub child_address: String,
+    pub target_amount: i64,
+    pub created_at_ms: i64,
+    pub deadline_ms: i64,
+    pub duration_days: i64,
+    pub current_balance: i64,
+    pub status: String,
+    pub can_withdraw: bool, // 已完成目标且超过截止时间，与合约 can_withdraw 一致
+    pub tx_digest: Option<String>,
+    pub event_seq: Option<i64>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Deposit {
+    pub id: Uuid,
+    pub bucky_bank_id: String,
+    pub amount: i64,
+    pub depositor: String,
+    pub created_at_ms: i64,
+    pub tx_digest: Option<String>,
+    pub event_seq: Option<i64>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct WithdrawalRequest {
+    pub id: i32,
+    pub request_id: String,
+    pub bucky_bank_id: String,
+    pub amount: i64,
+    pub requester: String,
+    pub reason: Option<String>, // 私密字段，只返回给存钱罐的家长、孩子或管理员
+    pub status: String,
+    pub approved_by: Option<String>,
+    pub created_at_ms: i64,
+    pub audit_at_ms: Option<i64>,
+    pub tx_digest: Option<String>,
+    pub event_seq: Option<i64>,
+    pub timestamp_ms: Option<i64>,
+    pub indexed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Withdrawal {
+    pub id: Uuid,
+    pub request_id: String,
+    pub bucky_bank_id: String,
+    pub amount: i64,
+    pub left_balance: i64,
+    pub withdrawer: String,
+    pub created_at_ms: i64,
+    pub tx_digest: Option<String>,
+    pub event_seq: Option<i64>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct BankReward {
+    pub id: Uuid,
+    pub bucky_bank_id: String,
+    pub banks_count: i64,
+    pub reward_receiver: String,
+    pub total_reward: i64,
+    pub bank_reward: i64,
+    pub total_balance: i64,
+    pub bank_balance: i64,
+    pub tx_digest: String,
+    pub event_seq: i64,
+    pub timestamp_ms: i64,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct RewardClaim {
+    pub id: Uuid,
+    pub bucky_bank_id: String,
+    pub reward_receiver: String,
+    pub reward_amount: i64,
+    pub created_at_ms: i64,
+    pub tx_digest: String,
+    pub event_seq: i64,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+/// 地址名下单个 BuckyBank 的汇总，金额字段均由流水表聚合
+#[derive(Debug, Serialize, FromRow)]
+pub struct PortfolioBank {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub bank: BuckyBank,
+    pub is_parent: bool,
+    pub is_child: bool,
+    pub total_deposited: i64,
+    pub total_withdrawn: i64,
+    pub progress_percent: f64, // current_balance 占 target_amount 的百分比，最多 100
+    pub pending_withdrawal_count: i64,
+    pub pending_withdrawal_amount: i64,
+    pub unclaimed_rewards: i64, // 已分配但尚未领取的奖励
+}
+
+/// 地址名下全部 BuckyBank 的合计
+#[derive(Debug, Default, Serialize)]
+pub struct PortfolioTotals {
+    pub bank_count: i64,
+    pub current_balance: i64,
+    pub target_amount: i64,
+    pub total_deposited: i64,
+    pub total_withdrawn: i64,
+    pub pending_withdrawal_count: i64,
+    pub pending_withdrawal_amount: i64,
+    pub unclaimed_rewards: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Portfolio {
+    pub address: String,
+    pub totals: PortfolioTotals,
+    pub banks: Vec<PortfolioBank>,
+}
+
+impl Portfolio {
+    pub fn new(address: String, banks: Vec<PortfolioBank>) -> Self {
+        let totals = banks.iter().fold(PortfolioTotals::default(), |mut totals, item| {
+            totals.bank_count += 1;
+            totals.current_balance += item.bank.current_balance;
+            totals.target_amount += item.bank.target_amount;
+            totals.total_deposited += item.total_deposited;
+            totals.total_withdrawn += item.total_withdrawn;
+            totals.pending_withdrawal_count += item.pending_withdrawal_count;
+            totals.pending_withdrawal_amount += item.pending_withdrawal_amount;
+            totals.unclaimed_rewards += item.unclaimed_rewards;
+            totals
+        });
+
+        Self {
+            address,
+            totals,
+            banks,
+        }
+    }
+}
+
+/// 平台统计，对应合约 GlobalStats，数据来自索引器刷新的 platform_stats 物化视图
+#[derive(Debug, Serialize, FromRow)]
+pub struct PlatformStats {
+    pub total_bucky_banks: i64,
+    pub active_bucky_banks: i64,
+    pub completed_bucky_banks: i64,
+    pub total_balance: i64,
+    pub total_deposits: i64,
+    pub total_deposit_amount: i64,
+    pub unique_depositors: i64,
+    pub total_withdrawals: i64,
+    pub total_withdrawal_amount: i64,
+    pub total_rewards_distributed: i64,
+    pub total_rewards_claimed: i64,
+    pub refreshed_at: DateTime<Utc>,
+}
+
+/// 时间序列中的一个区间，balance 为区间结束时的累计余额
+#[derive(Debug, Serialize, FromRow)]
+pub struct BalancePoint {
+    pub bucket_start_ms: i64,
+    pub deposited: i64,
+    pub withdrawn: i64,
+    pub balance: i64,
+    pub percent_of_target: f64,
+}
+
+/// BuckyBank 的余额时间序列和按当前储蓄速度推算的完成时间
+#[derive(Debug, Serialize)]
+pub struct BalanceTimeseries {
+    pub bucky_bank_id: String,
+    pub bucket: String,
+    pub target_amount: i64,
+    pub current_balance: i64,
+    pub percent_of_target: f64,
+    pub deadline_ms: i64,
+    pub daily_savings_rate: f64,                // 创建以来平均每天净存入的金额
+    pub projected_completion_ms: Option<i64>,   // 已达标时为达标区间的开始时间，无法推算时为空
+    pub on_track: Option<bool>,                 // 推算完成时间是否早于截止时间
+    pub points: Vec<BalancePoint>,
+}
+
+impl BalanceTimeseries {
+    pub fn new(bank: &BuckyBank, bucket: String, points: Vec<BalancePoint>, now_ms: i64) -> Self {
+        let percent_of_target = if bank.target_amount > 0 {
+            (bank.current_balance as f64 * 100.0 / bank.target_amount as f64).min(100.0)
+        } else {
+            0.0
+        };
+
+        // 不足一天按一天计算，避免刚创建时速度被放大
+        let elapsed_days = ((now_ms - bank.created_at_ms) as f64 / MS_PER_DAY).max(1.0);
+        let daily_savings_rate = bank.current_balance as f64 / elapsed_days;
+
+        let projected_completion_ms = if bank.current_balance >= bank.target_amount {
+            points
+                .iter()
+                .find(|point| point.balance >= bank.target_amount)
+                .map(|point| point.bucket_start_ms)
+        } else if daily_savings_rate > 0.0 {
+            let remaining_days = (bank.target_amount - bank.current_balance) as f64 / daily_savings_rate;
+            Some(now_ms + (remaining_days * MS_PER_DAY) as i64)
+        } else {
+            None
+        };
+
+        Self {
+            bucky_bank_id: bank.bucky_bank_id.clone(),
+            bucket,
+            target_amount: bank.target_amount,
+            current_balance: bank.current_balance,
+            percent_of_target,
+            deadline_ms: bank.deadline_ms,
+            daily_savings_rate,
+            projected_completion_ms,
+            on_track: projected_completion_ms.map(|completion_ms| completion_ms <= bank.deadline_ms),
+            points,
+        }
+    }
+}
+
+impl Keyset for BuckyBank {
+    fn page_cursor(&self) -> PageCursor {
+        PageCursor::new(self.created_at_ms, self.id)
+    }
+}
+
+impl Keyset for Deposit {
+    fn page_cursor(&self) -> PageCursor {
+        PageCursor::new(self.created_at_ms, self.id)
+    }
+}
+
+impl Keyset for WithdrawalRequest {
+    // withdrawal_requests 的主键是自增整数
+    const INTEGER_ID: bool = true;
+
+    fn page_cursor(&self) -> PageCursor {
+        PageCursor::new(self.created_at_ms, self.id)
+    }
+}
+
+impl Keyset for Withdrawal {
+    fn page_cursor(&self) -> PageCursor {
+        PageCursor::new(self.created_at_ms, self.id)
+    }
+}
+
+impl Keyset for BankReward {
+    fn page_cursor(&self) -> PageCursor {
+        PageCursor::new(self.timestamp_ms, self.id)
+    }
+}
+
+impl Keyset for RewardClaim {
+    fn page_cursor(&self) -> PageCursor {
+        PageCursor::new(self.created_at_ms, self.id)
+    }
+}
+
+// 查询参数中的分页字段没有使用 serde(flatten)，flatten 会导致 URL 查询中的数字无法解析
+// cursor 为上一页返回的 next_cursor，include_total 为 true 时额外返回总数
+
+#[derive(Debug, Default, Deserialize)]
+pub struct PageParams {
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    pub include_total: Option<bool>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BuckyBankQuery {
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    pub include_total: Option<bool>,
+    pub parent_address: Option<String>,
+    pub child_address: Option<String>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct WithdrawalRequestQuery {
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    pub include_total: Option<bool>,
+    pub bucky_bank_id: Option<String>,
+    pub requester: Option<String>,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct RewardQuery {
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    pub include_total: Option<bool>,
+    pub bucky_bank_id: Option<String>,
+    pub reward_receiver: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct TimeseriesQuery {
+    pub bucket: Option<String>,
+}
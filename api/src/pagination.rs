@@ -0,0 +1,209 @@
+use crate::error::ApiError;
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use std::fmt;
+use uuid::Uuid;
+
+/// 默认分页大小
+pub const DEFAULT_PAGE_SIZE: i64 = 10;
+
+/// 单页最大条数，超过时按最大值返回
+pub const MAX_PAGE_SIZE: i64 = 100;
+
+/// 游标中的主键，列表记录的主键为 UUID 或自增整数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CursorId {
+    Uuid(Uuid),
+    Integer(i32),
+}
+
+impl From<Uuid> for CursorId {
+    fn from(id: Uuid) -> Self {
+        CursorId::Uuid(id)
+    }
+}
+
+impl From<i32> for CursorId {
+    fn from(id: i32) -> Self {
+        CursorId::Integer(id)
+    }
+}
+
+impl fmt::Display for CursorId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CursorId::Uuid(id) => write!(f, "{}", id),
+            CursorId::Integer(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+/// 游标分页位置：最后一条记录的排序时间和主键
+///
+/// 对外以 base64 编码的不透明字符串传递，客户端只需原样带回 `cursor` 参数。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageCursor {
+    pub sort_key: i64,
+    pub id: CursorId,
+}
+
+impl PageCursor {
+    pub fn new(sort_key: i64, id: impl Into<CursorId>) -> Self {
+        Self {
+            sort_key,
+            id: id.into(),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!("{}:{}", self.sort_key, self.id))
+    }
+
+    /// 按列表记录的主键类型解析游标，伪造或来自其他列表的游标返回 400
+    pub fn decode<T: Keyset>(cursor: &str) -> Result<Self, ApiError> {
+        let invalid = || ApiError::validation(format!("Invalid cursor: {}", cursor));
+
+        let decoded = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+        let decoded = String::from_utf8(decoded).map_err(|_| invalid())?;
+        let (sort_key, id) = decoded.split_once(':').ok_or_else(invalid)?;
+
+        let id = if T::INTEGER_ID {
+            CursorId::Integer(id.parse().map_err(|_| invalid())?)
+        } else {
+            CursorId::Uuid(Uuid::parse_str(id).map_err(|_| invalid())?)
+        };
+
+        Ok(Self {
+            sort_key: sort_key.parse().map_err(|_| invalid())?,
+            id,
+        })
+    }
+}
+
+/// 可以按 (排序时间, 主键) 做游标分页的记录
+pub trait Keyset {
+    /// 主键是否为自增整数，默认为 UUID
+    const INTEGER_ID: bool = false;
+
+    fn page_cursor(&self) -> PageCursor;
+}
+
+/// 解析并校验后的分页参数
+///
+/// 提供 `cursor` 时按游标翻页并忽略 `page`，否则按 `page` 计算偏移量。
+/// 游标按列表记录类型 `T` 的主键类型解析。
+#[derive(Debug, Clone)]
+pub struct PageRequest {
+    pub limit: i64,
+    pub offset: i64,
+    pub after: Option<PageCursor>,
+    pub include_total: bool,
+}
+
+impl PageRequest {
+    pub fn new<T: Keyset>(
+        page: Option<i64>,
+        limit: Option<i64>,
+        cursor: Option<&str>,
+        include_total: bool,
+    ) -> Result<Self, ApiError> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let after = cursor.map(PageCursor::decode::<T>).transpose()?;
+        let offset = match after {
+            Some(_) => 0,
+            None => (page.unwrap_or(1).max(1) - 1).saturating_mul(limit),
+        };
+
+        Ok(Self {
+            limit,
+            offset,
+            after,
+            include_total,
+        })
+    }
+}
+
+/// 一页查询结果，`next_cursor` 为空表示没有下一页
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub total: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct UuidRow;
+
+    impl Keyset for UuidRow {
+        fn page_cursor(&self) -> PageCursor {
+            unreachable!()
+        }
+    }
+
+    struct IntegerRow;
+
+    impl Keyset for IntegerRow {
+        const INTEGER_ID: bool = true;
+
+        fn page_cursor(&self) -> PageCursor {
+            unreachable!()
+        }
+    }
+
+    fn raw(value: &str) -> String {
+        URL_SAFE_NO_PAD.encode(value)
+    }
+
+    #[test]
+    fn round_trips_both_id_types() {
+        let uuid_cursor = PageCursor::new(1760166241636, Uuid::new_v4());
+        assert_eq!(PageCursor::decode::<UuidRow>(&uuid_cursor.encode()).unwrap(), uuid_cursor);
+
+        let integer_cursor = PageCursor::new(1760166241636, 42);
+        assert_eq!(
+            PageCursor::decode::<IntegerRow>(&integer_cursor.encode()).unwrap(),
+            integer_cursor
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_cursors() {
+        let cursors = [
+            "not base64!".to_string(),
+            raw("1760166241636"),
+            raw("abc:42"),
+            raw("1760166241636:42 OR 1=1"),
+            // 超出 INTEGER 范围
+            raw("1760166241636:4294967296"),
+        ];
+        for cursor in &cursors {
+            assert!(
+                matches!(PageCursor::decode::<IntegerRow>(cursor), Err(ApiError::Validation { .. })),
+                "{}",
+                cursor
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_cursor_from_a_list_with_another_id_type() {
+        let uuid_cursor = PageCursor::new(1, Uuid::new_v4()).encode();
+        assert!(PageCursor::decode::<IntegerRow>(&uuid_cursor).is_err());
+
+        let integer_cursor = PageCursor::new(1, 42).encode();
+        assert!(PageCursor::decode::<UuidRow>(&integer_cursor).is_err());
+    }
+
+    #[test]
+    fn clamps_limit_and_page() {
+        let page = PageRequest::new::<UuidRow>(Some(0), Some(1_000_000), None, false).unwrap();
+        assert_eq!(page.limit, MAX_PAGE_SIZE);
+        assert_eq!(page.offset, 0);
+
+        let page = PageRequest::new::<UuidRow>(Some(3), Some(0), None, false).unwrap();
+        assert_eq!(page.limit, 1);
+        assert_eq!(page.offset, 2);
+    }
+}
@@ -0,0 +1,38 @@
+use crate::pagination::Page;
+use serde::Serialize;
+
+/// 统一响应格式
+///
+/// 列表接口在有下一页时返回 `next_cursor`，请求 `include_total=true` 时返回 `total`，
+/// 失败时返回 [`crate::ApiError`] 的错误响应体。
+#[derive(Debug, Serialize)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    pub data: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
+}
+
+impl<T> ApiResponse<T> {
+    pub fn success(data: T) -> Self {
+        Self {
+            success: true,
+            data: Some(data),
+            next_cursor: None,
+            total: None,
+        }
+    }
+}
+
+impl<T> ApiResponse<Vec<T>> {
+    pub fn page(page: Page<T>) -> Self {
+        Self {
+            success: true,
+            data: Some(page.items),
+            next_cursor: page.next_cursor,
+            total: page.total,
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use crate::auth::{Viewer, bearer_token};
+use crate::error::ApiError;
+use axum::{
+    extract::{
+        Query, State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::HeaderMap,
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use sqlx::postgres::PgListener;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio_stream::{Stream, StreamExt, wrappers::BroadcastStream};
+
+/// 索引器发布事件通知使用的 Postgres NOTIFY 频道
+pub const EVENT_CHANNEL: &str = "bucky_bank_events";
+
+// 每个订阅者最多缓存的通知数，消费过慢的订阅者会丢弃较早的通知
+const BROADCAST_CAPACITY: usize = 1024;
+
+/// 索引器写入一个事件后发布的通知
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventNotification {
+    pub event_type: String, // 与索引器 EventType 名称一致，如 DepositMade
+    pub bucky_bank_id: String,
+    pub addresses: Vec<String>, // 家长、孩子地址以及事件中的操作者地址
+    pub tx_digest: String,
+    pub event_seq: i64,
+    pub timestamp_ms: Option<i64>,
+    pub data: serde_json::Value, // 链上事件的 parsed_json
+}
+
+/// 订阅过滤条件，未指定的条件不做过滤
+#[derive(Debug, Default, Deserialize)]
+pub struct StreamFilter {
+    pub bucky_bank_id: Option<String>,
+    pub address: Option<String>,
+    /// 访问令牌，浏览器的 EventSource / WebSocket 无法设置请求头时通过查询参数传递
+    pub token: Option<String>,
+}
+
+impl StreamFilter {
+    pub fn matches(&self, notification: &EventNotification) -> bool {
+        self.bucky_bank_id
+            .as_ref()
+            .is_none_or(|id| *id == notification.bucky_bank_id)
+            && self
+                .address
+                .as_ref()
+                .is_none_or(|address| notification.addresses.contains(address))
+    }
+}
+
+// 订阅者不是家长、孩子或管理员时去掉事件中的提取原因
+fn redact(mut notification: EventNotification, viewer: &Viewer) -> EventNotification {
+    if !viewer.is_party(&notification.addresses)
+        && let Some(data) = notification.data.as_object_mut()
+    {
+        data.remove("reason");
+    }
+    notification
+}
+
+// 优先使用 Authorization 请求头，其次是 ?token= 查询参数
+async fn resolve_viewer(
+    pool: &PgPool,
+    headers: &HeaderMap,
+    filter: &StreamFilter,
+) -> Result<Viewer, ApiError> {
+    match bearer_token(headers).or(filter.token.as_deref()) {
+        Some(token) => Viewer::from_token(pool, token).await,
+        None => Ok(Viewer::Anonymous),
+    }
+}
+
+/// 监听 NOTIFY 频道并把通知广播给所有 SSE / WebSocket 订阅者
+pub struct EventStream {
+    sender: broadcast::Sender<EventNotification>,
+    pool: PgPool,
+}
+
+impl EventStream {
+    /// 创建广播通道并启动后台监听任务，连接断开后自动重连
+    pub fn spawn(pool: PgPool) -> Arc<Self> {
+        let (sender, _) = broadcast::channel(BROADCAST_CAPACITY);
+        let stream = Arc::new(Self { sender, pool });
+
+        let listener_stream = stream.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = listener_stream.listen(&listener_stream.pool).await {
+                    tracing::error!("Event notification listener failed, reconnecting: {}", e);
+                }
+                tokio::time::sleep(Duration::from_secs(5)).await;
+            }
+        });
+
+        stream
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<EventNotification> {
+        self.sender.subscribe()
+    }
+
+    async fn listen(&self, pool: &PgPool) -> anyhow::Result<()> {
+        let mut listener = PgListener::connect_with(pool).await?;
+        listener.listen(EVENT_CHANNEL).await?;
+        tracing::info!("Listening for event notifications on {}", EVENT_CHANNEL);
+
+        loop {
+            let notification = listener.recv().await?;
+            match serde_json::from_str::<EventNotification>(notification.payload()) {
+                // 没有订阅者时发送失败，直接忽略
+                Ok(event) => {
+                    let _ = self.sender.send(event);
+                }
+                Err(e) => tracing::warn!("Ignoring malformed event notification: {}", e),
+            }
+        }
+    }
+}
+
+// 通过 SSE 推送事件通知，支持 ?bucky_bank_id= 和 ?address= 过滤
+pub async fn stream_events(
+    State(stream): State<Arc<EventStream>>,
+    headers: HeaderMap,
+    Query(filter): Query<StreamFilter>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let viewer = resolve_viewer(&stream.pool, &headers, &filter).await?;
+    let events = BroadcastStream::new(stream.subscribe()).filter_map(move |received| {
+        match received {
+            Ok(notification) if filter.matches(&notification) => {
+                let notification = redact(notification, &viewer);
+                let event = Event::default().event(notification.event_type.clone());
+                event.json_data(&notification).ok().map(Ok)
+            }
+            Ok(_) => None,
+            Err(e) => {
+                tracing::warn!("SSE subscriber lagged behind: {}", e);
+                None
+            }
+        }
+    });
+
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+// 通过 WebSocket 推送事件通知，过滤条件与 SSE 相同，客户端发送的消息只处理关闭
+pub async fn websocket_events(
+    State(stream): State<Arc<EventStream>>,
+    headers: HeaderMap,
+    Query(filter): Query<StreamFilter>,
+    ws: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    let viewer = resolve_viewer(&stream.pool, &headers, &filter).await?;
+    Ok(ws
+        .on_upgrade(move |socket| forward_events(socket, stream.subscribe(), filter, viewer))
+        .into_response())
+}
+
+async fn forward_events(
+    mut socket: WebSocket,
+    mut receiver: broadcast::Receiver<EventNotification>,
+    filter: StreamFilter,
+    viewer: Viewer,
+) {
+    loop {
+        tokio::select! {
+            received = receiver.recv() => match received {
+                Ok(notification) if filter.matches(&notification) => {
+                    let text = match serde_json::to_string(&redact(notification, &viewer)) {
+                        Ok(text) => text,
+                        Err(e) => {
+                            tracing::warn!("Failed to serialize event notification: {}", e);
+                            continue;
+                        }
+                    };
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("WebSocket subscriber lagged behind, skipped {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // ping 由 axum 自动回复 pong
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
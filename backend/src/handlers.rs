@@ -1,232 +1,41 @@
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
-    response::Json,
+    extract::Path,
+    http::Uri,
+    response::{Json, Redirect},
 };
-use log::{info, error, warn};
+use bucky_bank_api::API_PREFIX;
+use log::info;
 use serde_json::{json, Value};
-use std::{sync::Arc, time::Instant};
 
-use crate::database::Database;
-use crate::models::{BuckyBankResponse, QueryParams, DepositResponse, DepositQueryParams, WithdrawalRequestResponse, WithdrawalRequestQueryParams, WithdrawedEventResponse, WithdrawedEventQueryParams};
-
-pub async fn get_bucky_banks(
-    State(db): State<Arc<Database>>,
-    Query(params): Query<QueryParams>,
-) -> Result<Json<Value>, StatusCode> {
-    let start_time = Instant::now();
-    info!("收到获取BuckyBanks列表请求 - 参数: {:?}", params);
-    
-    match db.get_bucky_banks(params).await {
-        Ok((events, total)) => {
-            let duration = start_time.elapsed();
-            info!("成功获取BuckyBanks列表 - 数量: {}, 总计: {}, 耗时: {:?}", 
-                  events.len(), total, duration);
-            
-            let response = BuckyBankResponse {
-                success: true,
-                data: events,
-                total,
-            };
-            Ok(Json(json!(response)))
-        }
-        Err(e) => {
-            let duration = start_time.elapsed();
-            error!("获取BuckyBanks列表失败 - 错误: {}, 耗时: {:?}", e, duration);
-            
-            Ok(Json(json!({
-                "success": false,
-                "error": "Failed to fetch bucky banks",
-                "data": [],
-                "total": 0
-            })))
-        }
-    }
-}
-
-pub async fn get_bucky_bank_by_id(
-    State(db): State<Arc<Database>>,
-    Path(bucky_bank_id): Path<String>,
-) -> Result<Json<Value>, StatusCode> {
-    let start_time = Instant::now();
-    info!("收到获取单个BuckyBank请求 - ID: {}", bucky_bank_id);
-    
-    match db.get_bucky_bank_by_id(&bucky_bank_id).await {
-        Ok(Some(event)) => {
-            let duration = start_time.elapsed();
-            info!("成功获取BuckyBank - ID: {}, 耗时: {:?}", bucky_bank_id, duration);
-            
-            Ok(Json(json!({
-                "success": true,
-                "data": event
-            })))
-        }
-        Ok(None) => {
-            let duration = start_time.elapsed();
-            warn!("BuckyBank未找到 - ID: {}, 耗时: {:?}", bucky_bank_id, duration);
-            
-            Ok(Json(json!({
-                "success": false,
-                "error": "BuckyBank not found",
-                "data": null
-            })))
-        }
-        Err(e) => {
-            let duration = start_time.elapsed();
-            error!("获取BuckyBank失败 - ID: {}, 错误: {}, 耗时: {:?}", 
-                   bucky_bank_id, e, duration);
-            
-            Ok(Json(json!({
-                "success": false,
-                "error": "Failed to fetch bucky bank",
-                "data": null
-            })))
-        }
+// 旧版接口默认返回总数，重定向时补上 include_total=true，客户端显式传入时保持不变
+fn v1_location(path: &str, query: Option<&str>) -> String {
+    let mut params: Vec<&str> = query
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .filter(|param| !param.is_empty())
+        .collect();
+    if !params.iter().any(|param| param.starts_with("include_total=")) {
+        params.push("include_total=true");
     }
+    format!("{}{}?{}", API_PREFIX, path, params.join("&"))
 }
 
-pub async fn get_deposits_by_bucky_bank_id(
-    State(db): State<Arc<Database>>,
-    Path(bucky_bank_id): Path<String>,
-    Query(params): Query<DepositQueryParams>,
-) -> Result<Json<Value>, StatusCode> {
-    let start_time = Instant::now();
-    info!("收到获取存款记录请求 - BuckyBank ID: {}, 参数: {:?}", bucky_bank_id, params);
-    
-    match db.get_deposits_by_bucky_bank_id(&bucky_bank_id, params).await {
-        Ok((deposits, total)) => {
-            let duration = start_time.elapsed();
-            info!("成功获取存款记录 - BuckyBank ID: {}, 数量: {}, 总计: {}, 耗时: {:?}", 
-                  bucky_bank_id, deposits.len(), total, duration);
-            
-            let response = DepositResponse {
-                success: true,
-                data: deposits,
-                total,
-            };
-            Ok(Json(json!(response)))
-        }
-        Err(e) => {
-            let duration = start_time.elapsed();
-            error!("获取存款记录失败 - BuckyBank ID: {}, 错误: {}, 耗时: {:?}", 
-                   bucky_bank_id, e, duration);
-            
-            Ok(Json(json!({
-                "success": false,
-                "error": "Failed to fetch deposits",
-                "data": [],
-                "total": 0
-            })))
-        }
-    }
-}
-
-pub async fn get_withdrawal_requests_by_bucky_bank_id(
-    State(db): State<Arc<Database>>,
-    Path(bucky_bank_id): Path<String>,
-    Query(params): Query<WithdrawalRequestQueryParams>,
-) -> Result<Json<Value>, StatusCode> {
-    let start_time = Instant::now();
-    info!("收到获取取款请求记录请求 - BuckyBank ID: {}, 参数: {:?}", bucky_bank_id, params);
-    
-    match db.get_withdrawal_requests_by_bucky_bank_id(&bucky_bank_id, params).await {
-        Ok((requests, total)) => {
-            let duration = start_time.elapsed();
-            info!("成功获取取款请求记录 - BuckyBank ID: {}, 数量: {}, 总计: {}, 耗时: {:?}", 
-                  bucky_bank_id, requests.len(), total, duration);
-            
-            let response = WithdrawalRequestResponse {
-                success: true,
-                data: requests,
-                total,
-            };
-            Ok(Json(json!(response)))
-        }
-        Err(e) => {
-            let duration = start_time.elapsed();
-            error!("获取取款请求记录失败 - BuckyBank ID: {}, 错误: {}, 耗时: {:?}", 
-                   bucky_bank_id, e, duration);
-            
-            Ok(Json(json!({
-                "success": false,
-                "error": "Failed to fetch withdrawal requests",
-                "data": [],
-                "total": 0
-            })))
-        }
-    }
+// 旧版 /api/bucky-banks/... 接口，永久重定向到 /api/v1 上的同名接口
+pub async fn redirect_to_v1(uri: Uri) -> Redirect {
+    let path = uri.path().strip_prefix("/api").unwrap_or(uri.path());
+    Redirect::permanent(&v1_location(path, uri.query()))
 }
 
-pub async fn get_withdrawal_requests_by_requester(
-    State(db): State<Arc<Database>>,
+// 旧版按申请人查询提取请求的接口，重定向到 /api/v1/withdrawal-requests?requester=
+pub async fn redirect_withdrawal_requests_by_requester(
     Path(requester): Path<String>,
-    Query(params): Query<WithdrawalRequestQueryParams>,
-) -> Result<Json<Value>, StatusCode> {
-    let start_time = Instant::now();
-    info!("收到获取用户取款请求记录请求 - 请求者: {}, 参数: {:?}", requester, params);
-    
-    match db.get_withdrawal_requests_by_requester(&requester, params).await {
-        Ok((requests, total)) => {
-            let duration = start_time.elapsed();
-            info!("成功获取用户取款请求记录 - 请求者: {}, 数量: {}, 总计: {}, 耗时: {:?}", 
-                  requester, requests.len(), total, duration);
-            
-            let response = WithdrawalRequestResponse {
-                success: true,
-                data: requests,
-                total,
-            };
-            Ok(Json(json!(response)))
-        }
-        Err(e) => {
-            let duration = start_time.elapsed();
-            error!("获取用户取款请求记录失败 - 请求者: {}, 错误: {}, 耗时: {:?}", 
-                   requester, e, duration);
-            
-            Ok(Json(json!({
-                "success": false,
-                "error": "Failed to fetch withdrawal requests",
-                "data": [],
-                "total": 0
-            })))
-        }
-    }
-}
-
-pub async fn get_withdrawals_by_bucky_bank_id(
-    State(db): State<Arc<Database>>,
-    Path(bucky_bank_id): Path<String>,
-    Query(params): Query<WithdrawedEventQueryParams>,
-) -> Result<Json<Value>, StatusCode> {
-    let start_time = Instant::now();
-    info!("收到获取取款记录请求 - BuckyBank ID: {}, 参数: {:?}", bucky_bank_id, params);
-    
-    match db.get_withdrawals_by_bucky_bank_id(&bucky_bank_id, params).await {
-        Ok((withdrawals, total)) => {
-            let duration = start_time.elapsed();
-            info!("成功获取取款记录 - BuckyBank ID: {}, 数量: {}, 总计: {}, 耗时: {:?}", 
-                  bucky_bank_id, withdrawals.len(), total, duration);
-            
-            let response = WithdrawedEventResponse {
-                success: true,
-                data: withdrawals,
-                total,
-            };
-            Ok(Json(json!(response)))
-        }
-        Err(e) => {
-            let duration = start_time.elapsed();
-            error!("获取取款记录失败 - BuckyBank ID: {}, 错误: {}, 耗时: {:?}", 
-                   bucky_bank_id, e, duration);
-            
-            Ok(Json(json!({
-                "success": false,
-                "error": "Failed to fetch withdrawals",
-                "data": [],
-                "total": 0
-            })))
-        }
-    }
+    uri: Uri,
+) -> Redirect {
+    let query = match uri.query() {
+        Some(query) => format!("requester={}&{}", requester, query),
+        None => format!("requester={}", requester),
+    };
+    Redirect::permanent(&v1_location("/withdrawal-requests", Some(&query)))
 }
 
 pub async fn health_check() -> Json<Value> {
@@ -236,4 +45,25 @@ pub async fn health_check() -> Json<Value> {
         "status": "ok",
         "message": "BuckyBank API is running"
     }))
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_query_and_defaults_total() {
+        assert_eq!(
+            v1_location("/bucky-banks", Some("page=2&limit=20")),
+            "/api/v1/bucky-banks?page=2&limit=20&include_total=true"
+        );
+        assert_eq!(
+            v1_location("/bucky-banks/0x1/deposits", None),
+            "/api/v1/bucky-banks/0x1/deposits?include_total=true"
+        );
+        assert_eq!(
+            v1_location("/withdrawal-requests", Some("requester=0x2&include_total=false")),
+            "/api/v1/withdrawal-requests?requester=0x2&include_total=false"
+        );
+    }
+}
@@ -1,6 +1,4 @@
-mod database;
 mod handlers;
-mod models;
 
 use axum::{
     Router,
@@ -12,7 +10,7 @@ use axum::{
 use dotenv::dotenv;
 use log::info;
 use sqlx::PgPool;
-use std::{env, sync::Arc, time::Instant};
+use std::{env, time::Instant};
 use tower_http::cors::CorsLayer;
 
 // 日志中间件
@@ -71,32 +69,25 @@ async fn main() -> anyhow::Result<()> {
     sqlx::migrate!("./migrations").run(&pool).await.ok();
     info!("Database migrations completed");
 
-    let db = Arc::new(database::Database::new(pool));
-
-    // 创建路由
+    // 创建路由，旧版 /api 接口重定向到 /api/v1
     let app = Router::new()
         .route("/health", get(handlers::health_check))
-        .route("/api/bucky-banks", get(handlers::get_bucky_banks))
-        .route("/api/bucky-banks/:id", get(handlers::get_bucky_bank_by_id))
-        .route(
-            "/api/bucky-banks/:id/deposits",
-            get(handlers::get_deposits_by_bucky_bank_id),
-        )
-        .route(
-            "/api/bucky-banks/:id/withdrawals",
-            get(handlers::get_withdrawals_by_bucky_bank_id),
-        )
+        .route("/api/bucky-banks", get(handlers::redirect_to_v1))
+        .route("/api/bucky-banks/:id", get(handlers::redirect_to_v1))
+        .route("/api/bucky-banks/:id/deposits", get(handlers::redirect_to_v1))
+        .route("/api/bucky-banks/:id/withdrawals", get(handlers::redirect_to_v1))
         .route(
             "/api/bucky-banks/:id/withdrawal-requests",
-            get(handlers::get_withdrawal_requests_by_bucky_bank_id),
+            get(handlers::redirect_to_v1),
         )
         .route(
             "/api/withdrawal-requests/requester/:requester",
-            get(handlers::get_withdrawal_requests_by_requester),
+            get(handlers::redirect_withdrawal_requests_by_requester),
         )
+        // 统一的 /api/v1 接口
+        .merge(bucky_bank_api::router(pool))
         .layer(middleware::from_fn(logging_middleware))
-        .layer(CorsLayer::permissive())
-        .with_state(db);
+        .layer(CorsLayer::permissive());
 
     // 启动服务器
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", port)).await?;
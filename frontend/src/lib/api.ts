@@ -42,7 +42,7 @@ class ApiClient {
     }
 
     async getBuckyBanks(params?: BuckyBankQueryParams): Promise<BuckyBankResponse> {
-        const searchParams = new URLSearchParams();
+        const searchParams = new URLSearchParams({ include_total: 'true' });
         
         if (params?.page) searchParams.append('page', params.page.toString());
         if (params?.limit) searchParams.append('limit', params.limit.toString());
@@ -50,41 +50,41 @@ class ApiClient {
         if (params?.child_address) searchParams.append('child_address', params.child_address);
 
         const queryString = searchParams.toString();
-        const endpoint = `/api/bucky-banks${queryString ? `?${queryString}` : ''}`;
+        const endpoint = `/api/v1/bucky-banks${queryString ? `?${queryString}` : ''}`;
         
         return this.request<BuckyBankResponse>(endpoint);
     }
 
     async getBuckyBankById(id: string): Promise<BuckyBankSingleResponse> {
-        return this.request<BuckyBankSingleResponse>(`/api/bucky-banks/${id}`);
+        return this.request<BuckyBankSingleResponse>(`/api/v1/bucky-banks/${id}`);
     }
 
     async getDepositsByBuckyBankId(id: string, params?: DepositQueryParams): Promise<DepositResponse> {
-        const searchParams = new URLSearchParams();
+        const searchParams = new URLSearchParams({ include_total: 'true' });
         
         if (params?.page) searchParams.append('page', params.page.toString());
         if (params?.limit) searchParams.append('limit', params.limit.toString());
 
         const queryString = searchParams.toString();
-        const endpoint = `/api/bucky-banks/${id}/deposits${queryString ? `?${queryString}` : ''}`;
+        const endpoint = `/api/v1/bucky-banks/${id}/deposits${queryString ? `?${queryString}` : ''}`;
         
         return this.request<DepositResponse>(endpoint);
     }
 
     async getWithdrawsByBuckyBankId(id: string, params?: WithdrawQueryParams): Promise<WithdrawResponse> {
-        const searchParams = new URLSearchParams();
+        const searchParams = new URLSearchParams({ include_total: 'true' });
         
         if (params?.page) searchParams.append('page', params.page.toString());
         if (params?.limit) searchParams.append('limit', params.limit.toString());
 
         const queryString = searchParams.toString();
-        const endpoint = `/api/bucky-banks/${id}/withdrawals${queryString ? `?${queryString}` : ''}`;
+        const endpoint = `/api/v1/bucky-banks/${id}/withdrawals${queryString ? `?${queryString}` : ''}`;
         
         return this.request<WithdrawResponse>(endpoint);
     }
 
     async getWithdrawalRequestsByBuckyBankId(id: string, params?: WithdrawalRequestQueryParams): Promise<WithdrawalRequestResponse> {
-        const searchParams = new URLSearchParams();
+        const searchParams = new URLSearchParams({ include_total: 'true' });
         
         if (params?.page) searchParams.append('page', params.page.toString());
         if (params?.limit) searchParams.append('limit', params.limit.toString());
@@ -92,20 +92,20 @@ class ApiClient {
         if (params?.requester) searchParams.append('requester', params.requester);
 
         const queryString = searchParams.toString();
-        const endpoint = `/api/bucky-banks/${id}/withdrawal-requests${queryString ? `?${queryString}` : ''}`;
+        const endpoint = `/api/v1/bucky-banks/${id}/withdrawal-requests${queryString ? `?${queryString}` : ''}`;
         
         return this.request<WithdrawalRequestResponse>(endpoint);
     }
 
     async getWithdrawalRequestsByRequester(requester: string, params?: WithdrawalRequestQueryParams): Promise<WithdrawalRequestResponse> {
-        const searchParams = new URLSearchParams();
+        const searchParams = new URLSearchParams({ include_total: 'true', requester });
         
         if (params?.page) searchParams.append('page', params.page.toString());
         if (params?.limit) searchParams.append('limit', params.limit.toString());
         if (params?.status) searchParams.append('status', params.status);
 
         const queryString = searchParams.toString();
-        const endpoint = `/api/withdrawal-requests/requester/${requester}${queryString ? `?${queryString}` : ''}`;
+        const endpoint = `/api/v1/withdrawal-requests${queryString ? `?${queryString}` : ''}`;
         
         return this.request<WithdrawalRequestResponse>(endpoint);
     }
@@ -83,7 +83,7 @@ export interface WithdrawalRequest {
     bucky_bank_id: string;
     amount: number;
     requester: string;
-    reason: string;
+    reason: string | null; // 未登录或不是家长、孩子时为 null
     status: 'Pending' | 'Approved' | 'Rejected' | 'Cancelled' | 'Withdrawed';
     approved_by?: string;
     created_at_ms: number;
@@ -0,0 +1,57 @@
+use crate::database::models::AdminApiKey;
+use crate::health::HealthState;
+use axum::{
+    extract::{Request, State},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use bucky_bank_api::{ApiError, auth};
+
+// 也可以通过该请求头传递 API Key
+const ADMIN_KEY_HEADER: &str = "x-admin-key";
+
+/// 生成新的管理接口 API Key，明文只在创建时输出一次
+pub fn generate_api_key() -> String {
+    auth::generate_token(auth::ADMIN_KEY_PREFIX)
+}
+
+/// 从 `Authorization: Bearer <key>` 或 `X-Admin-Key` 请求头读取 API Key
+fn api_key_from_headers(headers: &HeaderMap) -> Option<&str> {
+    auth::bearer_token(headers).or_else(|| {
+        headers
+            .get(ADMIN_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|key| !key.is_empty())
+    })
+}
+
+/// 管理接口认证中间件，认证通过后把 API Key 信息放入请求扩展，供处理函数写入审计记录
+pub async fn require_admin_key(
+    State(state): State<HealthState>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let key = api_key_from_headers(request.headers())
+        .ok_or_else(|| ApiError::unauthorized("Missing admin API key"))?;
+
+    let admin: AdminApiKey = match state.db.authenticate_admin_api_key(&auth::hash_token(key)).await {
+        Ok(Some(admin)) => admin,
+        Ok(None) => return Err(ApiError::unauthorized("Invalid or revoked admin API key")),
+        Err(e) => {
+            tracing::error!("Failed to authenticate admin API key: {}", e);
+            return Err(ApiError::database("Failed to authenticate admin API key", e));
+        }
+    };
+
+    tracing::info!(
+        "Admin request {} {} by {}",
+        request.method(),
+        request.uri().path(),
+        admin.name
+    );
+    request.extensions_mut().insert(admin);
+
+    Ok(next.run(request).await)
+}
@@ -0,0 +1,31 @@
+use anyhow::{Result, anyhow};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use shared_crypto::intent::{Intent, IntentMessage, PersonalMessage};
+use sui_sdk::types::base_types::SuiAddress;
+use sui_sdk::types::crypto::{Signature, SuiSignature, ToFromBytes};
+
+/// 校验钱包 `signPersonalMessage` 返回的签名
+///
+/// 签名为 base64 编码的 `flag || signature || public_key`，支持 Ed25519、Secp256k1 和 Secp256r1，
+/// 公钥推导出的地址必须与 `address` 一致。
+pub fn verify_personal_message(address: &str, message: &str, signature: &str) -> Result<()> {
+    let address: SuiAddress = address
+        .parse()
+        .map_err(|e| anyhow!("Invalid address {}: {}", address, e))?;
+    let bytes = STANDARD
+        .decode(signature)
+        .map_err(|e| anyhow!("Invalid signature encoding: {}", e))?;
+    let signature =
+        Signature::from_bytes(&bytes).map_err(|e| anyhow!("Invalid signature: {}", e))?;
+
+    let intent_message = IntentMessage::new(
+        Intent::personal_message(),
+        PersonalMessage {
+            message: message.as_bytes().to_vec(),
+        },
+    );
+
+    signature
+        .verify_secure(&intent_message, address, signature.scheme())
+        .map_err(|e| anyhow!("Signature verification failed: {}", e))
+}
@@ -0,0 +1,73 @@
+use crate::config::Config;
+use crate::database::Database;
+use crate::indexer::BuckyBankIndexer;
+use crate::sink;
+use crate::verifier::{BuckyBankReader, OnChainBuckyBank};
+use anyhow::Result;
+use std::sync::Arc;
+use sui_sdk::{SuiClient, SuiClientBuilder};
+use tokio::sync::OnceCell;
+use tracing::info;
+
+/// Sui 客户端和依赖它的事件索引器，在首次使用时创建后共用
+///
+/// `server` 模式只提供 HTTP 服务，只有调用重放或链上核对时才会连接 RPC、创建索引器和输出端。
+pub struct ChainServices {
+    config: Arc<Config>,
+    db: Arc<Database>,
+    sui_client: OnceCell<Arc<SuiClient>>,
+    indexer: OnceCell<Arc<BuckyBankIndexer>>,
+}
+
+impl ChainServices {
+    pub fn new(config: Arc<Config>, db: Arc<Database>) -> Self {
+        Self {
+            config,
+            db,
+            sui_client: OnceCell::new(),
+            indexer: OnceCell::new(),
+        }
+    }
+
+    pub async fn sui_client(&self) -> Result<Arc<SuiClient>> {
+        let client = self
+            .sui_client
+            .get_or_try_init(|| async {
+                info!("Connecting to Sui RPC {}", self.config.sui.rpc_url);
+                let client = SuiClientBuilder::default()
+                    .build(&self.config.sui.rpc_url)
+                    .await?;
+                Ok::<_, anyhow::Error>(Arc::new(client))
+            })
+            .await?;
+        Ok(client.clone())
+    }
+
+    pub async fn indexer(&self) -> Result<Arc<BuckyBankIndexer>> {
+        let indexer = self
+            .indexer
+            .get_or_try_init(|| async {
+                let sui_client = self.sui_client().await?;
+                let sink = sink::build_sink(&self.config.sinks, self.db.clone()).await?;
+
+                // 创建事件索引器
+                Ok::<_, anyhow::Error>(Arc::new(BuckyBankIndexer::new(
+                    sui_client,
+                    self.config.sui.package_id.clone(),
+                    self.config.sui.module_name.clone(),
+                    self.config.sui.query_limit,
+                    self.db.clone(),
+                    sink,
+                    &self.config.indexing,
+                )))
+            })
+            .await?;
+        Ok(indexer.clone())
+    }
+}
+
+impl BuckyBankReader for ChainServices {
+    async fn get_bucky_bank(&self, bucky_bank_id: &str) -> Result<Option<OnChainBuckyBank>> {
+        self.sui_client().await?.get_bucky_bank(bucky_bank_id).await
+    }
+}
@@ -8,6 +8,10 @@ pub struct Config {
     pub sui: SuiConfig,
     pub server: ServerConfig,
     pub indexing: IndexingConfig,
+    #[serde(default)]
+    pub webhook: WebhookConfig,
+    #[serde(default)]
+    pub sinks: SinkConfig,
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,6 +41,149 @@ pub struct IndexingConfig {
     pub poll_interval_seconds: u64,
     pub batch_size: usize,
     pub max_retries: u32,
+    /// 单个事件最多处理次数，超过后按 failure_policy 处理
+    #[serde(default = "default_max_event_attempts")]
+    pub max_event_attempts: u32,
+    /// 事件多次处理失败后的处理策略
+    #[serde(default)]
+    pub failure_policy: FailurePolicy,
+    /// 链上状态核对间隔(秒)，为 0 时不做定期核对
+    #[serde(default = "default_verify_interval_seconds")]
+    pub verify_interval_seconds: u64,
+    /// 索引延迟超过该值(秒)时 /ready 返回 503
+    #[serde(default = "default_ready_max_lag_seconds")]
+    pub ready_max_lag_seconds: u64,
+    /// 超过该时间(秒)没有成功轮询时 /ready 返回 503，应大于 poll_interval_seconds
+    #[serde(default = "default_ready_max_poll_age_seconds")]
+    pub ready_max_poll_age_seconds: u64,
+    /// 轮询任务异常退出后首次重启的等待时间(秒)，之后每次翻倍
+    #[serde(default = "default_restart_initial_backoff_seconds")]
+    pub restart_initial_backoff_seconds: u64,
+    /// 轮询任务重启等待时间上限(秒)
+    #[serde(default = "default_restart_max_backoff_seconds")]
+    pub restart_max_backoff_seconds: u64,
+    /// 事件内容的解析方式：json 解析 parsed_json，bcs 解析事件的 BCS 字节
+    #[serde(default)]
+    pub event_encoding: EventEncoding,
+}
+
+/// Webhook 投递配置
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    /// 是否启动投递任务，关闭时仍会记录待投递记录
+    pub enabled: bool,
+    /// 扫描待投递记录的间隔(秒)
+    pub poll_interval_seconds: u64,
+    /// 单条记录最多投递次数，超过后标记为 Failed
+    pub max_attempts: i32,
+    /// 单次请求超时时间(秒)
+    pub timeout_seconds: u64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            poll_interval_seconds: 5,
+            max_attempts: 8,
+            timeout_seconds: 10,
+        }
+    }
+}
+
+/// Postgres 之外的事件输出端，不配置时只写入 Postgres
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SinkConfig {
+    /// 追加写入的 JSON Lines 文件路径
+    pub jsonl_path: Option<String>,
+    /// Kafka REST Proxy 地址，如 http://localhost:8082
+    pub kafka_rest_url: Option<String>,
+    pub kafka_topic: String,
+    /// 单次发布请求超时时间(秒)
+    pub kafka_timeout_seconds: u64,
+}
+
+impl Default for SinkConfig {
+    fn default() -> Self {
+        Self {
+            jsonl_path: None,
+            kafka_rest_url: None,
+            kafka_topic: "bucky-bank-events".to_string(),
+            kafka_timeout_seconds: 10,
+        }
+    }
+}
+
+/// 事件多次处理失败后的处理策略
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FailurePolicy {
+    /// 停止索引器，游标停在失败事件之前
+    #[default]
+    Halt,
+    /// 写入 event_failures 死信表，游标继续前移
+    DeadLetter,
+}
+
+impl std::str::FromStr for FailurePolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "halt" => Ok(FailurePolicy::Halt),
+            "dead_letter" => Ok(FailurePolicy::DeadLetter),
+            _ => Err(format!("Invalid failure policy: {}", s)),
+        }
+    }
+}
+
+/// 事件内容的解析方式
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventEncoding {
+    /// 解析 RPC 返回的 parsed_json
+    #[default]
+    Json,
+    /// 解析事件的 BCS 字节，死信重放等没有 BCS 字节的事件仍按 JSON 解析
+    Bcs,
+}
+
+impl std::str::FromStr for EventEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(EventEncoding::Json),
+            "bcs" => Ok(EventEncoding::Bcs),
+            _ => Err(format!("Invalid event encoding: {}", s)),
+        }
+    }
+}
+
+fn default_max_event_attempts() -> u32 {
+    3
+}
+
+fn default_verify_interval_seconds() -> u64 {
+    300
+}
+
+fn default_ready_max_lag_seconds() -> u64 {
+    600
+}
+
+fn default_ready_max_poll_age_seconds() -> u64 {
+    300
+}
+
+fn default_restart_initial_backoff_seconds() -> u64 {
+    5
+}
+
+fn default_restart_max_backoff_seconds() -> u64 {
+    300
 }
 
 impl Config {
@@ -56,6 +203,7 @@ impl Config {
                 "SUI_PACKAGE_ID environment variable is required"
             ));
         }
+        config.validate()?;
 
         Ok(config)
     }
@@ -112,10 +260,20 @@ impl Config {
         if config.sui.package_id.is_empty() {
             return Err(anyhow::anyhow!("package_id 配置项是必需的"));
         }
+        config.validate()?;
 
         Ok(config)
     }
 
+    // 校验取值范围，轮询间隔为 0 会让轮询循环不停地请求 RPC
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.indexing.poll_interval_seconds == 0 {
+            return Err(anyhow::anyhow!("indexing.poll_interval_seconds 必须大于 0"));
+        }
+
+        Ok(())
+    }
+
     /// 从文件或环境变量加载配置
     pub fn load(config_file: Option<String>) -> anyhow::Result<Self> {
         match config_file {
@@ -168,7 +326,8 @@ impl Config {
                 poll_interval_seconds: env::var("INDEXING_POLL_INTERVAL")
                     .unwrap_or_else(|_| "30".to_string())
                     .parse()
-                    .unwrap_or(30),
+                    .unwrap_or(30)
+                    .max(1),
                 batch_size: env::var("INDEXING_BATCH_SIZE")
                     .unwrap_or_else(|_| "100".to_string())
                     .parse()
@@ -177,6 +336,66 @@ impl Config {
                     .unwrap_or_else(|_| "3".to_string())
                     .parse()
                     .unwrap_or(3),
+                max_event_attempts: env::var("INDEXING_MAX_EVENT_ATTEMPTS")
+                    .unwrap_or_else(|_| "3".to_string())
+                    .parse()
+                    .unwrap_or(3),
+                failure_policy: env::var("INDEXING_FAILURE_POLICY")
+                    .unwrap_or_else(|_| "halt".to_string())
+                    .parse()
+                    .unwrap_or_default(),
+                verify_interval_seconds: env::var("INDEXING_VERIFY_INTERVAL")
+                    .unwrap_or_else(|_| "300".to_string())
+                    .parse()
+                    .unwrap_or(300),
+                ready_max_lag_seconds: env::var("INDEXING_READY_MAX_LAG")
+                    .unwrap_or_else(|_| "600".to_string())
+                    .parse()
+                    .unwrap_or(600),
+                ready_max_poll_age_seconds: env::var("INDEXING_READY_MAX_POLL_AGE")
+                    .unwrap_or_else(|_| "300".to_string())
+                    .parse()
+                    .unwrap_or(300),
+                restart_initial_backoff_seconds: env::var("INDEXING_RESTART_INITIAL_BACKOFF")
+                    .unwrap_or_else(|_| "5".to_string())
+                    .parse()
+                    .unwrap_or(5),
+                restart_max_backoff_seconds: env::var("INDEXING_RESTART_MAX_BACKOFF")
+                    .unwrap_or_else(|_| "300".to_string())
+                    .parse()
+                    .unwrap_or(300),
+                event_encoding: env::var("INDEXING_EVENT_ENCODING")
+                    .unwrap_or_else(|_| "json".to_string())
+                    .parse()
+                    .unwrap_or_default(),
+            },
+            webhook: WebhookConfig {
+                enabled: env::var("WEBHOOK_ENABLED")
+                    .unwrap_or_else(|_| "true".to_string())
+                    .parse()
+                    .unwrap_or(true),
+                poll_interval_seconds: env::var("WEBHOOK_POLL_INTERVAL")
+                    .unwrap_or_else(|_| "5".to_string())
+                    .parse()
+                    .unwrap_or(5),
+                max_attempts: env::var("WEBHOOK_MAX_ATTEMPTS")
+                    .unwrap_or_else(|_| "8".to_string())
+                    .parse()
+                    .unwrap_or(8),
+                timeout_seconds: env::var("WEBHOOK_TIMEOUT")
+                    .unwrap_or_else(|_| "10".to_string())
+                    .parse()
+                    .unwrap_or(10),
+            },
+            sinks: SinkConfig {
+                jsonl_path: env::var("SINK_JSONL_PATH").ok(),
+                kafka_rest_url: env::var("SINK_KAFKA_REST_URL").ok(),
+                kafka_topic: env::var("SINK_KAFKA_TOPIC")
+                    .unwrap_or_else(|_| "bucky-bank-events".to_string()),
+                kafka_timeout_seconds: env::var("SINK_KAFKA_TIMEOUT")
+                    .unwrap_or_else(|_| "10".to_string())
+                    .parse()
+                    .unwrap_or(10),
             },
         }
     }
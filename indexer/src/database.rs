@@ -1,11 +1,18 @@
 use crate::config::DatabaseConfig;
+use crate::indexer::TARGET_REACHED_EVENT;
 use anyhow::Result;
+use bucky_bank_api::pagination::{CursorId, Keyset, Page, PageRequest};
+use chrono::DateTime;
+use bucky_bank_api::{EVENT_CHANNEL, EventNotification};
+use sqlx::PgConnection;
 use sqlx::PgPool;
 use sqlx::Row;
-use sqlx::postgres::PgPoolOptions;
+use sqlx::postgres::{PgPoolOptions, PgRow};
+use sqlx::{FromRow, Postgres, QueryBuilder};
 use std::time::Duration;
 
 pub mod models {
+    use bucky_bank_api::pagination::{Keyset, PageCursor};
     use chrono::{DateTime, Utc};
     use serde::{Deserialize, Serialize};
     use sqlx::FromRow;
@@ -22,10 +29,13 @@ pub mod models {
         pub deadline_ms: i64,
         pub duration_days: i64,
         pub current_balance: i64,
+        pub status: String,
+        pub tx_digest: Option<String>,
+        pub event_seq: Option<i64>,
         pub created_at: DateTime<Utc>,
     }
 
-    #[derive(Debug, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct NewBuckyBankCreatedEvent {
         pub bucky_bank_id: String,
         pub name: String,
@@ -36,6 +46,8 @@ pub mod models {
         pub deadline_ms: i64,
         pub duration_days: i64,
         pub current_balance: i64,
+        pub tx_digest: String,
+        pub event_seq: i64,
     }
 
     #[derive(Debug, FromRow, Serialize, Deserialize)]
@@ -45,15 +57,19 @@ pub mod models {
         pub amount: i64,
         pub depositor: String,
         pub created_at_ms: i64,
+        pub tx_digest: Option<String>,
+        pub event_seq: Option<i64>,
         pub created_at: DateTime<Utc>,
     }
 
-    #[derive(Debug, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct NewDepositMadeEvent {
         pub bucky_bank_id: String,
         pub amount: i64,
         pub depositor: String,
         pub created_at_ms: i64,
+        pub tx_digest: String,
+        pub event_seq: i64,
     }
 
     #[derive(Debug, FromRow, Serialize, Deserialize)]
@@ -65,7 +81,7 @@ pub mod models {
         pub updated_at: DateTime<Utc>,
     }
 
-    #[derive(Debug, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct NewCursor {
         pub id: String,
         pub event_seq: String,
@@ -120,16 +136,13 @@ pub mod models {
         pub approved_by: Option<String>,
         pub created_at_ms: i64,
         pub audit_at_ms: Option<i64>,        // 审批时间
+        pub tx_digest: Option<String>,
+        pub event_seq: Option<i64>,
+        pub timestamp_ms: Option<i64>,
         pub indexed_at: Option<DateTime<Utc>>,
     }
 
-    impl WithdrawalRequestEvent {
-        pub fn get_status(&self) -> Result<WithdrawalStatus, String> {
-            self.status.parse()
-        }
-    }
-
-    #[derive(Debug, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct NewWithdrawalRequestEvent {
         pub request_id: String,
         pub bucky_bank_id: String,
@@ -155,10 +168,12 @@ pub mod models {
         pub left_balance: i64,
         pub withdrawer: String,
         pub created_at_ms: i64,
+        pub tx_digest: Option<String>,
+        pub event_seq: Option<i64>,
         pub created_at: DateTime<Utc>,
     }
 
-    #[derive(Debug, Serialize, Deserialize)]
+    #[derive(Debug, Clone, Serialize, Deserialize)]
     pub struct NewEventWithdrawedEvent {
         pub request_id: String,
         pub bucky_bank_id: String,
@@ -166,6 +181,233 @@ pub mod models {
         pub left_balance: i64,
         pub withdrawer: String,
         pub created_at_ms: i64,
+        pub tx_digest: String,
+        pub event_seq: i64,
+    }
+
+    // EventBankReward 事件相关结构体
+    #[derive(Debug, FromRow, Serialize, Deserialize)]
+    pub struct BankRewardEvent {
+        pub id: Uuid,
+        pub bucky_bank_id: String,
+        pub banks_count: i64,
+        pub reward_receiver: String,
+        pub total_reward: i64,
+        pub bank_reward: i64,
+        pub total_balance: i64,
+        pub bank_balance: i64,
+        pub tx_digest: String,
+        pub event_seq: i64,
+        pub timestamp_ms: i64,
+        pub created_at: DateTime<Utc>,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct NewBankRewardEvent {
+        pub bucky_bank_id: String,
+        pub banks_count: i64,
+        pub reward_receiver: String,
+        pub total_reward: i64,
+        pub bank_reward: i64,
+        pub total_balance: i64,
+        pub bank_balance: i64,
+        pub tx_digest: String,
+        pub event_seq: i64,
+        pub timestamp_ms: i64,
+    }
+
+    // EventClaimReward 事件相关结构体
+    #[derive(Debug, FromRow, Serialize, Deserialize)]
+    pub struct RewardClaimEvent {
+        pub id: Uuid,
+        pub bucky_bank_id: String,
+        pub reward_receiver: String,
+        pub reward_amount: i64,
+        pub created_at_ms: i64,
+        pub tx_digest: String,
+        pub event_seq: i64,
+        pub created_at: DateTime<Utc>,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct NewRewardClaimEvent {
+        pub bucky_bank_id: String,
+        pub reward_receiver: String,
+        pub reward_amount: i64,
+        pub created_at_ms: i64,
+        pub tx_digest: String,
+        pub event_seq: i64,
+    }
+
+    // 事件处理失败（死信）相关结构体
+    #[derive(Debug, FromRow, Serialize, Deserialize)]
+    pub struct EventFailure {
+        pub id: Uuid,
+        pub tx_digest: String,
+        pub event_seq: i64,
+        pub event_type: String,
+        pub parsed_json: serde_json::Value,
+        pub error: String,
+        pub attempts: i32,
+        pub timestamp_ms: Option<i64>,
+        pub created_at: DateTime<Utc>,
+        pub updated_at: DateTime<Utc>,
+        pub resolved_at: Option<DateTime<Utc>>,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct NewEventFailure {
+        pub tx_digest: String,
+        pub event_seq: i64,
+        pub event_type: String,
+        pub parsed_json: serde_json::Value,
+        pub error: String,
+        pub attempts: i32,
+        pub timestamp_ms: Option<i64>,
+    }
+
+    // 余额对账相关结构体
+    #[derive(Debug, FromRow, Serialize, Deserialize)]
+    pub struct BalanceReconciliation {
+        pub bucky_bank_id: String,
+        pub stored_balance: i64,            // bucky_bank_created_events 中记录的余额
+        pub total_deposits: i64,
+        pub total_withdrawals: i64,
+        pub computed_balance: i64,          // 存款总额 - 提取总额
+        pub latest_left_balance: Option<i64>, // 最近一次 EventWithdrawed 的剩余余额
+        pub expected_balance: Option<i64>,  // 最近一次剩余余额 + 其后的存款，没有提取记录时为空
+    }
+
+    impl BalanceReconciliation {
+        /// 记录的余额与存取款流水不一致
+        pub fn has_stored_drift(&self) -> bool {
+            self.stored_balance != self.computed_balance
+        }
+
+        /// 存取款流水与链上提取事件给出的余额不一致，通常意味着有事件缺失
+        pub fn has_ledger_drift(&self) -> bool {
+            self.expected_balance
+                .is_some_and(|expected| expected != self.computed_balance)
+        }
+    }
+
+    // 按索引事件推导出的BuckyBank状态，用于与链上对象核对
+    #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+    pub struct IndexedBuckyBankState {
+        pub bucky_bank_id: String,
+        pub current_balance: i64,
+        pub status: String,
+        pub deposit_count: i64,
+        pub last_deposit_ms: i64,
+    }
+
+    #[derive(Debug, Default, Serialize)]
+    pub struct ReconcileReport {
+        pub checked: usize,
+        pub fixed: usize,
+        pub drifted: Vec<BalanceReconciliation>,
+    }
+
+    // Webhook 相关结构体
+    #[derive(Debug, FromRow, Serialize, Deserialize)]
+    pub struct WebhookSubscription {
+        pub id: Uuid,
+        pub url: String, // 签名密钥不读出，只在创建时返回一次
+        pub event_types: Vec<String>,
+        pub bucky_bank_id: Option<String>,
+        pub active: bool,
+        pub created_at: Option<DateTime<Utc>>,
+        pub updated_at: Option<DateTime<Utc>>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub struct NewWebhookSubscription {
+        pub url: String,
+        pub secret: Option<String>, // 不传时自动生成，只在创建时返回一次
+        #[serde(default)]
+        pub event_types: Vec<String>,
+        pub bucky_bank_id: Option<String>,
+    }
+
+    #[derive(Debug, FromRow, Serialize, Deserialize)]
+    pub struct WebhookDelivery {
+        pub id: Uuid,
+        pub subscription_id: Uuid,
+        pub event_type: String,
+        pub payload: serde_json::Value,
+        pub status: String,
+        pub attempts: i32,
+        pub next_attempt_at: DateTime<Utc>,
+        pub last_status_code: Option<i32>,
+        pub last_error: Option<String>,
+        pub delivered_at: Option<DateTime<Utc>>,
+        pub created_at: DateTime<Utc>,
+    }
+
+    // 管理接口 API Key，不包含摘要
+    #[derive(Debug, Clone, FromRow, Serialize)]
+    pub struct AdminApiKey {
+        pub id: Uuid,
+        pub name: String,
+        pub created_at: Option<DateTime<Utc>>,
+        pub last_used_at: Option<DateTime<Utc>>,
+        pub revoked_at: Option<DateTime<Utc>>,
+    }
+
+    // 提取请求状态人工修正审计记录
+    #[derive(Debug, FromRow, Serialize, Deserialize)]
+    pub struct WithdrawalStatusCorrection {
+        pub id: Uuid,
+        pub request_id: String,
+        pub previous_status: String,
+        pub corrected_status: String,
+        pub reason: String,
+        pub corrected_by: String,
+        pub created_at: DateTime<Utc>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub struct NewWithdrawalStatusCorrection {
+        pub status: String,
+        pub expected_status: String, // 调用方认为的当前状态，与实际不一致时拒绝修正
+        pub reason: String,
+    }
+
+    #[derive(Debug)]
+    pub enum CorrectionOutcome {
+        Corrected(WithdrawalStatusCorrection),
+        NotFound,
+        StatusMismatch(String), // 实际的当前状态
+    }
+
+    // 领取到的待投递记录，带上订阅的地址和签名密钥
+    #[derive(Debug, FromRow)]
+    pub struct PendingWebhookDelivery {
+        pub id: Uuid,
+        pub event_type: String,
+        pub payload: serde_json::Value,
+        pub attempts: i32,
+        pub url: String,
+        pub secret: String,
+    }
+
+    // 管理接口列表的游标排序键为 created_at 的微秒时间戳，与数据库时间精度一致
+    impl Keyset for EventFailure {
+        fn page_cursor(&self) -> PageCursor {
+            PageCursor::new(self.created_at.timestamp_micros(), self.id)
+        }
+    }
+
+    impl Keyset for WebhookDelivery {
+        fn page_cursor(&self) -> PageCursor {
+            PageCursor::new(self.created_at.timestamp_micros(), self.id)
+        }
+    }
+
+    impl Keyset for WithdrawalStatusCorrection {
+        fn page_cursor(&self) -> PageCursor {
+            PageCursor::new(self.created_at.timestamp_micros(), self.id)
+        }
     }
 }
 
@@ -188,16 +430,23 @@ impl Database {
         Ok(Self { pool })
     }
 
+    // 保存 BuckyBankCreated 事件，同一存钱罐重复写入时返回 None。
+    // 按 bucky_bank_id 去重，升级前写入、没有事件ID的记录在重放时同样会被跳过
     pub async fn save_bucky_bank_created_event(
         &self,
         event: &models::NewBuckyBankCreatedEvent,
-    ) -> Result<models::BuckyBankCreatedEvent> {
+        cursor: Option<&models::NewCursor>,
+    ) -> Result<Option<models::BuckyBankCreatedEvent>> {
+        let mut transaction = self.pool.begin().await?;
+
         let result = sqlx::query_as::<_, models::BuckyBankCreatedEvent>(
             r#"
             INSERT INTO bucky_bank_created_events (
                 bucky_bank_id, name, parent_address, child_address,
-                target_amount, created_at_ms, deadline_ms, duration_days, current_balance
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                target_amount, created_at_ms, deadline_ms, duration_days, current_balance,
+                tx_digest, event_seq
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            ON CONFLICT (bucky_bank_id) DO NOTHING
             RETURNING *
             "#,
         )
@@ -210,24 +459,40 @@ impl Database {
         .bind(event.deadline_ms)
         .bind(event.duration_days)
         .bind(event.current_balance)
-        .fetch_one(&self.pool)
+        .bind(&event.tx_digest)
+        .bind(event.event_seq)
+        .fetch_optional(&mut *transaction)
         .await?;
 
+        Self::advance_cursor(&mut transaction, cursor).await?;
+
+        transaction.commit().await?;
         Ok(result)
     }
 
+    // 保存 DepositMade 事件并同步余额，同一事件ID重复写入时返回 None 且不再累加余额。
+    // 传入通知时，存款使存钱罐达成目标则在同一事务内生成 TargetReached 的 webhook 投递记录
     pub async fn save_deposit_made_event(
         &self,
         event: &models::NewDepositMadeEvent,
-    ) -> Result<models::DepositMadeEvent> {
+        cursor: Option<&models::NewCursor>,
+        notification: Option<&EventNotification>,
+    ) -> Result<Option<models::DepositMadeEvent>> {
         let mut transaction = self.pool.begin().await?;
 
+        if Self::claim_legacy_deposit(&mut transaction, event).await? {
+            Self::advance_cursor(&mut transaction, cursor).await?;
+            transaction.commit().await?;
+            return Ok(None);
+        }
+
         // 1. 插入存款事件
         let deposit_result = sqlx::query_as::<_, models::DepositMadeEvent>(
             r#"
             INSERT INTO deposit_made_events (
-                bucky_bank_id, amount, depositor, created_at_ms
-            ) VALUES ($1, $2, $3, $4)
+                bucky_bank_id, amount, depositor, created_at_ms, tx_digest, event_seq
+            ) VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (tx_digest, event_seq) DO NOTHING
             RETURNING *
             "#,
         )
@@ -235,30 +500,51 @@ impl Database {
         .bind(event.amount)
         .bind(&event.depositor)
         .bind(event.created_at_ms)
-        .fetch_one(&mut *transaction)
+        .bind(&event.tx_digest)
+        .bind(event.event_seq)
+        .fetch_optional(&mut *transaction)
         .await?;
 
-        // 2. 同步更新bucky_bank的当前余额
-        let update_result = sqlx::query(
-            r#"
-            UPDATE bucky_bank_created_events
-            SET current_balance = current_balance + $1
-            WHERE bucky_bank_id = $2
-            "#,
-        )
-        .bind(event.amount)
-        .bind(&event.bucky_bank_id)
-        .execute(&mut *transaction)
-        .await?;
+        // 2. 同步更新bucky_bank的当前余额（仅在首次写入时），达到目标金额时状态变为 Completed，
+        //    并返回本次存款是否使存钱罐刚好达成目标
+        if deposit_result.is_some() {
+            let reached_target = sqlx::query_scalar::<_, bool>(
+                r#"
+                UPDATE bucky_bank_created_events
+                SET current_balance = current_balance + $1,
+                    status = CASE
+                        WHEN status = 'Active' AND current_balance + $1 >= target_amount THEN 'Completed'
+                        ELSE status
+                    END
+                WHERE bucky_bank_id = $2
+                RETURNING status = 'Completed' AND current_balance - $1 < target_amount
+                "#,
+            )
+            .bind(event.amount)
+            .bind(&event.bucky_bank_id)
+            .fetch_optional(&mut *transaction)
+            .await?;
 
-        // 检查是否成功更新了余额
-        if update_result.rows_affected() == 0 {
-            return Err(anyhow::anyhow!(
-                "BuckyBank with id {} not found",
-                event.bucky_bank_id
-            ));
+            // 检查是否成功更新了余额
+            let Some(reached_target) = reached_target else {
+                return Err(anyhow::anyhow!(
+                    "BuckyBank with id {} not found",
+                    event.bucky_bank_id
+                ));
+            };
+
+            if reached_target && let Some(notification) = notification {
+                let target_reached = EventNotification {
+                    event_type: TARGET_REACHED_EVENT.to_string(),
+                    ..notification.clone()
+                };
+                Self::enqueue_webhook_deliveries(&mut transaction, &target_reached).await?;
+            }
         }
 
+        // 3. 与事件写入同一事务内推进游标
+        Self::advance_cursor(&mut transaction, cursor).await?;
+
         transaction.commit().await?;
         Ok(deposit_result)
     }
@@ -271,24 +557,31 @@ impl Database {
 
         let mut count = 0;
         for event in events {
-            sqlx::query(
+            let result = sqlx::query(
                 r#"
                 INSERT INTO bucky_bank_created_events (
-                    bucky_bank_id, parent_address, child_address,
-                    target_amount, deadline_ms
-                ) VALUES ($1, $2, $3, $4, $5)
+                    bucky_bank_id, name, parent_address, child_address,
+                    target_amount, created_at_ms, deadline_ms, duration_days, current_balance,
+                    tx_digest, event_seq
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
                 ON CONFLICT (bucky_bank_id) DO NOTHING
                 "#,
             )
             .bind(&event.bucky_bank_id)
+            .bind(&event.name)
             .bind(&event.parent_address)
             .bind(&event.child_address)
             .bind(event.target_amount)
+            .bind(event.created_at_ms)
             .bind(event.deadline_ms)
+            .bind(event.duration_days)
+            .bind(event.current_balance)
+            .bind(&event.tx_digest)
+            .bind(event.event_seq)
             .execute(&mut *transaction)
             .await?;
 
-            count += 1;
+            count += result.rows_affected();
         }
 
         transaction.commit().await?;
@@ -303,26 +596,41 @@ impl Database {
 
         let mut count = 0;
         for event in events {
-            // 1. 插入存款事件
-            sqlx::query(
+            if Self::claim_legacy_deposit(&mut transaction, event).await? {
+                continue;
+            }
+
+            // 1. 插入存款事件，已存在的事件直接跳过
+            let insert_result = sqlx::query(
                 r#"
                 INSERT INTO deposit_made_events (
-                    bucky_bank_id, amount, depositor, created_at_ms
-                ) VALUES ($1, $2, $3, $4)
+                    bucky_bank_id, amount, depositor, created_at_ms, tx_digest, event_seq
+                ) VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (tx_digest, event_seq) DO NOTHING
                 "#,
             )
             .bind(&event.bucky_bank_id)
             .bind(event.amount)
             .bind(&event.depositor)
             .bind(event.created_at_ms)
+            .bind(&event.tx_digest)
+            .bind(event.event_seq)
             .execute(&mut *transaction)
             .await?;
 
-            // 2. 同步更新对应BuckyBank的余额
+            if insert_result.rows_affected() == 0 {
+                continue;
+            }
+
+            // 2. 同步更新对应BuckyBank的余额，达到目标金额时状态变为 Completed
             let update_result = sqlx::query(
                 r#"
                 UPDATE bucky_bank_created_events
-                SET current_balance = current_balance + $1
+                SET current_balance = current_balance + $1,
+                    status = CASE
+                        WHEN status = 'Active' AND current_balance + $1 >= target_amount THEN 'Completed'
+                        ELSE status
+                    END
                 WHERE bucky_bank_id = $2
                 "#,
             )
@@ -378,6 +686,15 @@ impl Database {
     }
 
     pub async fn save_cursor(&self, cursor: &models::NewCursor) -> Result<models::Cursor> {
+        let mut conn = self.pool.acquire().await?;
+        Self::upsert_cursor(&mut conn, cursor).await
+    }
+
+    // 写入游标，可在事件写入的事务内调用，保证事件与游标原子提交
+    async fn upsert_cursor(
+        conn: &mut PgConnection,
+        cursor: &models::NewCursor,
+    ) -> Result<models::Cursor> {
         let result = sqlx::query_as::<_, models::Cursor>(
             r#"
             INSERT INTO cursors (id, event_seq, tx_digest)
@@ -392,156 +709,331 @@ impl Database {
         .bind(&cursor.id)
         .bind(&cursor.event_seq)
         .bind(&cursor.tx_digest)
-        .fetch_one(&self.pool)
+        .fetch_one(conn)
         .await?;
 
         Ok(result)
     }
 
-    pub async fn update_cursor(
-        &self,
-        id: &str,
-        event_seq: &str,
-        tx_digest: &str,
-    ) -> Result<Option<models::Cursor>> {
-        let result = sqlx::query_as::<_, models::Cursor>(
+    // 在事件写入事务内推进游标；重放死信事件时不传游标，游标保持不变
+    async fn advance_cursor(
+        conn: &mut PgConnection,
+        cursor: Option<&models::NewCursor>,
+    ) -> Result<()> {
+        if let Some(cursor) = cursor {
+            Self::upsert_cursor(conn, cursor).await?;
+        }
+        Ok(())
+    }
+
+    // 升级前写入的存款记录没有事件ID，重放到该事件时按事件内容认领记录并补上事件ID。
+    // 返回 true 表示事件已写入过，不再插入也不再累加余额
+    async fn claim_legacy_deposit(
+        conn: &mut PgConnection,
+        event: &models::NewDepositMadeEvent,
+    ) -> Result<bool> {
+        let result = sqlx::query(
             r#"
-            UPDATE cursors SET
-                event_seq = $2,
-                tx_digest = $3,
-                updated_at = CURRENT_TIMESTAMP
-            WHERE id = $1
-            RETURNING *
+            UPDATE deposit_made_events
+            SET tx_digest = $5, event_seq = $6
+            WHERE id = (
+                SELECT id FROM deposit_made_events
+                WHERE tx_digest IS NULL
+                  AND bucky_bank_id = $1 AND amount = $2 AND depositor = $3 AND created_at_ms = $4
+                LIMIT 1
+            )
             "#,
         )
-        .bind(id)
-        .bind(event_seq)
-        .bind(tx_digest)
-        .fetch_optional(&self.pool)
+        .bind(&event.bucky_bank_id)
+        .bind(event.amount)
+        .bind(&event.depositor)
+        .bind(event.created_at_ms)
+        .bind(&event.tx_digest)
+        .bind(event.event_seq)
+        .execute(conn)
         .await?;
 
-        Ok(result)
+        Ok(result.rows_affected() > 0)
     }
 
-    // 查询BuckyBank的当前余额
-    pub async fn get_bucky_bank_balance(&self, bucky_bank_id: &str) -> Result<Option<i64>> {
+    // 同上，提取完成事件每个请求只有一个，按 request_id 认领升级前的记录
+    async fn claim_legacy_withdrawed(
+        conn: &mut PgConnection,
+        event: &models::NewEventWithdrawedEvent,
+    ) -> Result<bool> {
         let result = sqlx::query(
-            "SELECT current_balance FROM bucky_bank_created_events WHERE bucky_bank_id = $1",
+            r#"
+            UPDATE withdrawed_events
+            SET tx_digest = $2, event_seq = $3
+            WHERE request_id = $1 AND tx_digest IS NULL
+            "#,
         )
-        .bind(bucky_bank_id)
-        .fetch_optional(&self.pool)
+        .bind(&event.request_id)
+        .bind(&event.tx_digest)
+        .bind(event.event_seq)
+        .execute(conn)
         .await?;
 
-        Ok(result.map(|row| row.get::<i64, _>("current_balance")))
+        Ok(result.rows_affected() > 0)
     }
 
-    // 直接更新BuckyBank余额
-    pub async fn update_bucky_bank_balance(
-        &self,
-        bucky_bank_id: &str,
-        new_balance: i64,
-    ) -> Result<bool> {
+    // 在事件写入事务内为匹配事件类型和存钱罐的有效订阅生成投递记录，事件写入失败时投递记录一并回滚
+    async fn enqueue_webhook_deliveries(
+        conn: &mut PgConnection,
+        notification: &EventNotification,
+    ) -> Result<u64> {
+        let payload = serde_json::to_value(notification)?;
         let result = sqlx::query(
-            "UPDATE bucky_bank_created_events SET current_balance = $1 WHERE bucky_bank_id = $2",
+            r#"
+            INSERT INTO webhook_deliveries (subscription_id, event_type, payload)
+            SELECT id, $1, $3
+            FROM webhook_subscriptions
+            WHERE active
+              AND (cardinality(event_types) = 0 OR $1 = ANY(event_types))
+              AND (bucky_bank_id IS NULL OR bucky_bank_id = $2)
+            "#,
         )
-        .bind(new_balance)
-        .bind(bucky_bank_id)
-        .execute(&self.pool)
+        .bind(&notification.event_type)
+        .bind(&notification.bucky_bank_id)
+        .bind(&payload)
+        .execute(conn)
         .await?;
 
-        Ok(result.rows_affected() > 0)
+        if result.rows_affected() > 0 {
+            tracing::debug!(
+                "Queued {} {} webhook deliveries",
+                result.rows_affected(),
+                notification.event_type
+            );
+        }
+        Ok(result.rows_affected())
     }
 
-    // 通过存款事件重新计算并更新余额（用于数据一致性修复）
+    // 通过存取款事件重新计算并更新余额（用于数据一致性修复）
     pub async fn recalculate_bucky_bank_balance(&self, bucky_bank_id: &str) -> Result<bool> {
-        // 重新计算总存款金额
-        let total_deposits: Option<i64> = sqlx::query(
-            "SELECT COALESCE(SUM(amount), 0) as total_amount FROM deposit_made_events WHERE bucky_bank_id = $1"
+        // 余额 = 存款总额 - 提取总额，在同一条语句内计算，避免与事件写入交错
+        let result = sqlx::query(
+            r#"
+            UPDATE bucky_bank_created_events b
+            SET current_balance =
+                COALESCE((SELECT SUM(amount) FROM deposit_made_events WHERE bucky_bank_id = b.bucky_bank_id), 0)::BIGINT
+                - COALESCE((SELECT SUM(amount) FROM withdrawed_events WHERE bucky_bank_id = b.bucky_bank_id), 0)::BIGINT
+            WHERE b.bucky_bank_id = $1
+            "#,
         )
         .bind(bucky_bank_id)
-        .fetch_optional(&self.pool)
-        .await?
-        .map(|row| row.get::<i64, _>("total_amount"));
+        .execute(&self.pool)
+        .await?;
 
-        if let Some(total_amount) = total_deposits {
-            // 更新余额
-            let result = sqlx::query(
-                "UPDATE bucky_bank_created_events SET current_balance = $1 WHERE bucky_bank_id = $2"
-            )
-            .bind(total_amount)
-            .bind(bucky_bank_id)
-            .execute(&self.pool)
-            .await?;
+        Ok(result.rows_affected() > 0)
+    }
 
-            Ok(result.rows_affected() > 0)
-        } else {
-            // 如果没有存款记录，将余额设置为0
-            let result = sqlx::query(
-                "UPDATE bucky_bank_created_events SET current_balance = 0 WHERE bucky_bank_id = $1",
+    // 按存取款流水核对BuckyBank余额，bucky_bank_id 为空时核对全部
+    pub async fn get_balance_reconciliations(
+        &self,
+        bucky_bank_id: Option<&str>,
+    ) -> Result<Vec<models::BalanceReconciliation>> {
+        let result = sqlx::query_as::<_, models::BalanceReconciliation>(
+            r#"
+            WITH deposits AS (
+                SELECT bucky_bank_id, SUM(amount)::BIGINT AS total
+                FROM deposit_made_events
+                GROUP BY bucky_bank_id
+            ),
+            withdrawals AS (
+                SELECT bucky_bank_id, SUM(amount)::BIGINT AS total
+                FROM withdrawed_events
+                GROUP BY bucky_bank_id
+            ),
+            latest_withdrawals AS (
+                SELECT DISTINCT ON (bucky_bank_id) bucky_bank_id, left_balance, created_at_ms
+                FROM withdrawed_events
+                ORDER BY bucky_bank_id, created_at_ms DESC, event_seq DESC NULLS LAST
             )
-            .bind(bucky_bank_id)
-            .execute(&self.pool)
-            .await?;
+            SELECT
+                b.bucky_bank_id,
+                b.current_balance AS stored_balance,
+                COALESCE(d.total, 0) AS total_deposits,
+                COALESCE(w.total, 0) AS total_withdrawals,
+                COALESCE(d.total, 0) - COALESCE(w.total, 0) AS computed_balance,
+                lw.left_balance AS latest_left_balance,
+                lw.left_balance + (
+                    SELECT COALESCE(SUM(dm.amount), 0)::BIGINT
+                    FROM deposit_made_events dm
+                    WHERE dm.bucky_bank_id = b.bucky_bank_id
+                      AND dm.created_at_ms > lw.created_at_ms
+                ) AS expected_balance
+            FROM bucky_bank_created_events b
+            LEFT JOIN deposits d ON d.bucky_bank_id = b.bucky_bank_id
+            LEFT JOIN withdrawals w ON w.bucky_bank_id = b.bucky_bank_id
+            LEFT JOIN latest_withdrawals lw ON lw.bucky_bank_id = b.bucky_bank_id
+            WHERE $1::VARCHAR IS NULL OR b.bucky_bank_id = $1
+            ORDER BY b.created_at_ms
+            "#,
+        )
+        .bind(bucky_bank_id)
+        .fetch_all(&self.pool)
+        .await?;
 
-            Ok(result.rows_affected() > 0)
-        }
+        Ok(result)
     }
 
-    pub async fn delete_cursor(&self, id: &str) -> Result<bool> {
-        let result = sqlx::query("DELETE FROM cursors WHERE id = $1")
-            .bind(id)
-            .execute(&self.pool)
-            .await?;
-
-        Ok(result.rows_affected() > 0)
-    }
+    // 对账并返回存在偏差的BuckyBank，fix 为 true 时按存取款流水修正记录的余额
+    pub async fn reconcile_bucky_bank_balances(
+        &self,
+        bucky_bank_id: Option<&str>,
+        fix: bool,
+    ) -> Result<models::ReconcileReport> {
+        let reconciliations = self.get_balance_reconciliations(bucky_bank_id).await?;
 
-    pub async fn list_cursors(&self, limit: Option<i64>) -> Result<Vec<models::Cursor>> {
-        let query = if let Some(limit) = limit {
-            "SELECT * FROM cursors ORDER BY updated_at DESC LIMIT $1"
-        } else {
-            "SELECT * FROM cursors ORDER BY updated_at DESC"
+        let mut report = models::ReconcileReport {
+            checked: reconciliations.len(),
+            ..Default::default()
         };
 
-        let mut query_builder = sqlx::query_as::<_, models::Cursor>(query);
+        for reconciliation in reconciliations {
+            if !reconciliation.has_stored_drift() && !reconciliation.has_ledger_drift() {
+                continue;
+            }
+
+            if fix
+                && reconciliation.has_stored_drift()
+                && self
+                    .recalculate_bucky_bank_balance(&reconciliation.bucky_bank_id)
+                    .await?
+            {
+                report.fixed += 1;
+            }
 
-        if let Some(limit) = limit {
-            query_builder = query_builder.bind(limit);
+            report.drifted.push(reconciliation);
         }
 
-        let result = query_builder.fetch_all(&self.pool).await?;
-        Ok(result)
+        Ok(report)
     }
 
-    // WithdrawalRequest 相关方法
-    pub async fn save_withdrawal_request_event(
+    // 查询BuckyBank的家长和孩子地址
+    pub async fn get_bucky_bank_addresses(
         &self,
-        event: &models::NewWithdrawalRequestEvent,
-    ) -> Result<models::WithdrawalRequestEvent> {
-        let result = sqlx::query_as::<_, models::WithdrawalRequestEvent>(
-            r#"
-            INSERT INTO withdrawal_requests (
-                request_id, bucky_bank_id, amount, requester, reason,
-                status, approved_by, created_at_ms, audit_at_ms
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
-            RETURNING *
-            "#,
+        bucky_bank_id: &str,
+    ) -> Result<Option<(String, String)>> {
+        let result = sqlx::query_as::<_, (String, String)>(
+            "SELECT parent_address, child_address FROM bucky_bank_created_events WHERE bucky_bank_id = $1",
         )
-        .bind(&event.request_id)
-        .bind(&event.bucky_bank_id)
-        .bind(event.amount)
-        .bind(&event.requester)
-        .bind(&event.reason)
-        .bind(event.status.to_string())
-        .bind(&event.approved_by)
-        .bind(event.created_at_ms)
-        .bind(event.audit_at_ms) // 添加audit_at_ms字段
-        .fetch_one(&self.pool)
+        .bind(bucky_bank_id)
+        .fetch_optional(&self.pool)
         .await?;
 
         Ok(result)
     }
 
+    // 通过 NOTIFY 发布事件通知，供 /api/v1/stream 和 /api/v1/ws 推送给客户端
+    pub async fn notify_event(&self, notification: &EventNotification) -> Result<()> {
+        sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(EVENT_CHANNEL)
+            .bind(serde_json::to_string(notification)?)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    // 刷新平台统计物化视图，CONCURRENTLY 刷新期间不阻塞 /api/v1/stats 的读取
+    pub async fn refresh_platform_stats(&self) -> Result<()> {
+        sqlx::query("REFRESH MATERIALIZED VIEW CONCURRENTLY platform_stats")
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    // 查询全部BuckyBank ID，按创建时间排序
+    pub async fn get_bucky_bank_ids(&self) -> Result<Vec<String>> {
+        let result = sqlx::query_scalar::<_, String>(
+            "SELECT bucky_bank_id FROM bucky_bank_created_events ORDER BY created_at_ms",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    // 查询BuckyBank的状态、存款次数和最近存款时间，用于与链上对象核对
+    pub async fn get_indexed_bucky_bank_state(
+        &self,
+        bucky_bank_id: &str,
+    ) -> Result<Option<models::IndexedBuckyBankState>> {
+        let result = sqlx::query_as::<_, models::IndexedBuckyBankState>(
+            r#"
+            WITH deposits AS (
+                SELECT COUNT(*) AS deposit_count, MAX(created_at_ms) AS last_deposit_ms
+                FROM deposit_made_events
+                WHERE bucky_bank_id = $1
+            )
+            SELECT
+                b.bucky_bank_id,
+                b.current_balance,
+                b.status,
+                d.deposit_count,
+                COALESCE(d.last_deposit_ms, b.created_at_ms) AS last_deposit_ms
+            FROM bucky_bank_created_events b
+            CROSS JOIN deposits d
+            WHERE b.bucky_bank_id = $1
+            "#,
+        )
+        .bind(bucky_bank_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    // WithdrawalRequest 相关方法
+    // 按 request_id 去重，升级前写入、没有事件ID的记录在重放时同样会被跳过
+    // 传入通知时，新写入的请求在同一事务内生成 webhook 投递记录
+    pub async fn save_withdrawal_request_event(
+        &self,
+        event: &models::NewWithdrawalRequestEvent,
+        cursor: Option<&models::NewCursor>,
+        notification: Option<&EventNotification>,
+    ) -> Result<Option<models::WithdrawalRequestEvent>> {
+        let mut transaction = self.pool.begin().await?;
+
+        let result = sqlx::query_as::<_, models::WithdrawalRequestEvent>(
+            r#"
+            INSERT INTO withdrawal_requests (
+                request_id, bucky_bank_id, amount, requester, reason,
+                status, approved_by, created_at_ms, audit_at_ms,
+                tx_digest, event_seq, timestamp_ms
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            ON CONFLICT (request_id) DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(&event.request_id)
+        .bind(&event.bucky_bank_id)
+        .bind(event.amount)
+        .bind(&event.requester)
+        .bind(&event.reason)
+        .bind(event.status.to_string())
+        .bind(&event.approved_by)
+        .bind(event.created_at_ms)
+        .bind(event.audit_at_ms) // 添加audit_at_ms字段
+        .bind(&event.tx_digest)
+        .bind(event.event_seq)
+        .bind(event.timestamp_ms)
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        if result.is_some() && let Some(notification) = notification {
+            Self::enqueue_webhook_deliveries(&mut transaction, notification).await?;
+        }
+
+        Self::advance_cursor(&mut transaction, cursor).await?;
+
+        transaction.commit().await?;
+        Ok(result)
+    }
+
     pub async fn save_withdrawal_request_events_batch(
         &self,
         events: &[models::NewWithdrawalRequestEvent],
@@ -550,12 +1042,12 @@ impl Database {
 
         let mut count = 0;
         for event in events {
-            sqlx::query(
+            let result = sqlx::query(
                 r#"
                 INSERT INTO withdrawal_requests (
                     request_id, bucky_bank_id, amount, requester, reason,
-                    status, approved_by, created_at_ms
-                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                    status, approved_by, created_at_ms, tx_digest, event_seq, timestamp_ms
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
                 ON CONFLICT (request_id) DO NOTHING
                 "#,
             )
@@ -567,189 +1059,158 @@ impl Database {
             .bind(event.status.to_string())
             .bind(&event.approved_by)
             .bind(event.created_at_ms)
+            .bind(&event.tx_digest)
+            .bind(event.event_seq)
+            .bind(event.timestamp_ms)
             .execute(&mut *transaction)
             .await?;
 
-            count += 1;
+            count += result.rows_affected();
         }
 
         transaction.commit().await?;
         Ok(count)
     }
 
-    // 根据request_id查询提取请求
-    pub async fn get_withdrawal_request_by_id(
+    // 更新提取请求状态
+    // 人工修正提取请求状态，在同一事务中写入审计记录；当前状态与 expected_status 不一致时不做修改
+    pub async fn correct_withdrawal_request_status(
         &self,
         request_id: &str,
-    ) -> Result<Option<models::WithdrawalRequestEvent>> {
-        let result = sqlx::query_as::<_, models::WithdrawalRequestEvent>(
-            "SELECT * FROM withdrawal_requests WHERE request_id = $1"
+        correction: &models::NewWithdrawalStatusCorrection,
+        corrected_by: &str,
+    ) -> Result<models::CorrectionOutcome> {
+        let mut transaction = self.pool.begin().await?;
+
+        let current_status = sqlx::query_scalar::<_, String>(
+            "SELECT status FROM withdrawal_requests WHERE request_id = $1 FOR UPDATE",
         )
         .bind(request_id)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *transaction)
         .await?;
 
-        Ok(result)
-    }
+        let current_status = match current_status {
+            Some(status) if status == correction.expected_status => status,
+            Some(status) => return Ok(models::CorrectionOutcome::StatusMismatch(status)),
+            None => return Ok(models::CorrectionOutcome::NotFound),
+        };
 
-    // 根据bucky_bank_id查询提取请求列表
-    pub async fn get_withdrawal_requests_by_bank_id(
-        &self,
-        bucky_bank_id: &str,
-        limit: Option<i64>,
-        offset: Option<i64>,
-    ) -> Result<Vec<models::WithdrawalRequestEvent>> {
-        let query = r#"
-            SELECT * FROM withdrawal_requests 
-            WHERE bucky_bank_id = $1 
-            ORDER BY created_at_ms DESC 
-            LIMIT $2 OFFSET $3
-        "#;
-
-        let result = sqlx::query_as::<_, models::WithdrawalRequestEvent>(query)
-            .bind(bucky_bank_id)
-            .bind(limit.unwrap_or(50))
-            .bind(offset.unwrap_or(0))
-            .fetch_all(&self.pool)
+        sqlx::query("UPDATE withdrawal_requests SET status = $1 WHERE request_id = $2")
+            .bind(&correction.status)
+            .bind(request_id)
+            .execute(&mut *transaction)
             .await?;
 
-        Ok(result)
-    }
-
-    // 根据requester查询提取请求列表
-    pub async fn get_withdrawal_requests_by_requester(
-        &self,
-        requester: &str,
-        limit: Option<i64>,
-        offset: Option<i64>,
-    ) -> Result<Vec<models::WithdrawalRequestEvent>> {
-        let query = r#"
-            SELECT * FROM withdrawal_requests 
-            WHERE requester = $1 
-            ORDER BY created_at_ms DESC 
-            LIMIT $2 OFFSET $3
-        "#;
-
-        let result = sqlx::query_as::<_, models::WithdrawalRequestEvent>(query)
-            .bind(requester)
-            .bind(limit.unwrap_or(50))
-            .bind(offset.unwrap_or(0))
-            .fetch_all(&self.pool)
-            .await?;
+        let record = sqlx::query_as::<_, models::WithdrawalStatusCorrection>(
+            r#"
+            INSERT INTO withdrawal_status_corrections
+                (request_id, previous_status, corrected_status, reason, corrected_by)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(request_id)
+        .bind(&current_status)
+        .bind(&correction.status)
+        .bind(&correction.reason)
+        .bind(corrected_by)
+        .fetch_one(&mut *transaction)
+        .await?;
 
-        Ok(result)
+        transaction.commit().await?;
+        Ok(models::CorrectionOutcome::Corrected(record))
     }
 
-    // 根据状态查询提取请求列表
-    pub async fn get_withdrawal_requests_by_status(
+    pub async fn get_withdrawal_status_corrections(
         &self,
-        status: &models::WithdrawalStatus,
-        limit: Option<i64>,
-        offset: Option<i64>,
-    ) -> Result<Vec<models::WithdrawalRequestEvent>> {
-        let query = r#"
-            SELECT * FROM withdrawal_requests 
-            WHERE status = $1 
-            ORDER BY created_at_ms DESC 
-            LIMIT $2 OFFSET $3
-        "#;
-
-        let result = sqlx::query_as::<_, models::WithdrawalRequestEvent>(query)
-            .bind(status.to_string())
-            .bind(limit.unwrap_or(50))
-            .bind(offset.unwrap_or(0))
-            .fetch_all(&self.pool)
-            .await?;
-
-        Ok(result)
+        request_id: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<Page<models::WithdrawalStatusCorrection>> {
+        self.fetch_admin_page(
+            "withdrawal_status_corrections",
+            |query| {
+                if let Some(request_id) = request_id {
+                    query.push(" AND request_id = ").push_bind(request_id.to_string());
+                }
+            },
+            page,
+        )
+        .await
     }
 
-    // 更新提取请求状态
-    pub async fn update_withdrawal_request_status(
+    // 应用链上审批/拒绝事件，仅 Pending 状态的请求会被更新。
+    // 返回 false 表示该请求已经审批过（事件重放），此时只推进游标。
+    // 传入通知时，审批生效后在同一事务内生成 webhook 投递记录。
+    pub async fn apply_withdrawal_audit_event(
         &self,
         request_id: &str,
         status: &models::WithdrawalStatus,
-        approved_by: Option<&str>,
-        audit_at_ms: Option<i64>,
+        audited_by: &str,
+        audit_at_ms: i64,
+        cursor: Option<&models::NewCursor>,
+        notification: Option<&EventNotification>,
     ) -> Result<bool> {
+        let mut transaction = self.pool.begin().await?;
+
         let result = sqlx::query(
             r#"
-            UPDATE withdrawal_requests 
+            UPDATE withdrawal_requests
             SET status = $1, approved_by = $2, audit_at_ms = $3
-            WHERE request_id = $4
+            WHERE request_id = $4 AND status = 'Pending'
             "#,
         )
         .bind(status.to_string())
-        .bind(approved_by)
+        .bind(audited_by)
         .bind(audit_at_ms)
         .bind(request_id)
-        .execute(&self.pool)
+        .execute(&mut *transaction)
         .await?;
 
-        Ok(result.rows_affected() > 0)
-    }
+        let applied = result.rows_affected() > 0;
+        if !applied {
+            let exists = sqlx::query("SELECT 1 FROM withdrawal_requests WHERE request_id = $1")
+                .bind(request_id)
+                .fetch_optional(&mut *transaction)
+                .await?
+                .is_some();
 
-    // 获取提取请求统计信息
-    pub async fn get_withdrawal_requests_stats(
-        &self,
-        bucky_bank_id: Option<&str>,
-    ) -> Result<serde_json::Value> {
-        let query = if bucky_bank_id.is_some() {
-            r#"
-            SELECT 
-                status,
-                COUNT(*) as count,
-                COALESCE(SUM(amount), 0) as total_amount
-            FROM withdrawal_requests 
-            WHERE bucky_bank_id = $1
-            GROUP BY status
-            "#
-        } else {
-            r#"
-            SELECT 
-                status,
-                COUNT(*) as count,
-                COALESCE(SUM(amount), 0) as total_amount
-            FROM withdrawal_requests 
-            GROUP BY status
-            "#
-        };
-
-        let mut query_builder = sqlx::query(query);
-        if let Some(bank_id) = bucky_bank_id {
-            query_builder = query_builder.bind(bank_id);
+            if !exists {
+                return Err(anyhow::anyhow!("Withdrawal request not found: {}", request_id));
+            }
+        } else if let Some(notification) = notification {
+            Self::enqueue_webhook_deliveries(&mut transaction, notification).await?;
         }
 
-        let rows = query_builder.fetch_all(&self.pool).await?;
-
-        let mut stats = serde_json::Map::new();
-        for row in rows {
-            let status: String = row.get("status");
-            let count: i64 = row.get("count");
-            let total_amount: i64 = row.get("total_amount");
-            
-            stats.insert(status, serde_json::json!({
-                "count": count,
-                "total_amount": total_amount
-            }));
-        }
+        Self::advance_cursor(&mut transaction, cursor).await?;
 
-        Ok(serde_json::Value::Object(stats))
+        transaction.commit().await?;
+        Ok(applied)
     }
 
     // EventWithdrawed 事件相关方法
+    // 传入通知时，新写入的事件在同一事务内生成 webhook 投递记录
     pub async fn save_event_withdrawed_event(
         &self,
         event: &models::NewEventWithdrawedEvent,
-    ) -> Result<models::EventWithdrawedEvent> {
+        cursor: Option<&models::NewCursor>,
+        notification: Option<&EventNotification>,
+    ) -> Result<Option<models::EventWithdrawedEvent>> {
         let mut transaction = self.pool.begin().await?;
 
+        if Self::claim_legacy_withdrawed(&mut transaction, event).await? {
+            Self::advance_cursor(&mut transaction, cursor).await?;
+            transaction.commit().await?;
+            return Ok(None);
+        }
+
         // 1. 插入提取完成事件
         let withdrawed_result = sqlx::query_as::<_, models::EventWithdrawedEvent>(
             r#"
             INSERT INTO withdrawed_events (
-                request_id, bucky_bank_id, amount, left_balance, withdrawer, created_at_ms
-            ) VALUES ($1, $2, $3, $4, $5, $6)
+                request_id, bucky_bank_id, amount, left_balance, withdrawer, created_at_ms,
+                tx_digest, event_seq
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (tx_digest, event_seq) DO NOTHING
             RETURNING *
             "#,
         )
@@ -759,9 +1220,18 @@ impl Database {
         .bind(event.left_balance)
         .bind(&event.withdrawer)
         .bind(event.created_at_ms)
-        .fetch_one(&mut *transaction)
+        .bind(&event.tx_digest)
+        .bind(event.event_seq)
+        .fetch_optional(&mut *transaction)
         .await?;
 
+        // 事件已写入过，不再重复更新状态和余额
+        if withdrawed_result.is_none() {
+            Self::advance_cursor(&mut transaction, cursor).await?;
+            transaction.commit().await?;
+            return Ok(None);
+        }
+
         // 2. 同步更新对应提取请求的状态为Withdrawed
         let update_result = sqlx::query(
             r#"
@@ -804,6 +1274,13 @@ impl Database {
             ));
         }
 
+        if let Some(notification) = notification {
+            Self::enqueue_webhook_deliveries(&mut transaction, notification).await?;
+        }
+
+        // 4. 与事件写入同一事务内推进游标
+        Self::advance_cursor(&mut transaction, cursor).await?;
+
         transaction.commit().await?;
         Ok(withdrawed_result)
     }
@@ -816,13 +1293,18 @@ impl Database {
 
         let mut count = 0;
         for event in events {
-            // 1. 插入提取完成事件
-            sqlx::query(
+            if Self::claim_legacy_withdrawed(&mut transaction, event).await? {
+                continue;
+            }
+
+            // 1. 插入提取完成事件，已存在的事件直接跳过
+            let insert_result = sqlx::query(
                 r#"
                 INSERT INTO withdrawed_events (
-                    request_id, bucky_bank_id, amount, left_balance, withdrawer, created_at_ms
-                ) VALUES ($1, $2, $3, $4, $5, $6)
-                ON CONFLICT DO NOTHING
+                    request_id, bucky_bank_id, amount, left_balance, withdrawer, created_at_ms,
+                    tx_digest, event_seq
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (tx_digest, event_seq) DO NOTHING
                 "#,
             )
             .bind(&event.request_id)
@@ -831,9 +1313,15 @@ impl Database {
             .bind(event.left_balance)
             .bind(&event.withdrawer)
             .bind(event.created_at_ms)
+            .bind(&event.tx_digest)
+            .bind(event.event_seq)
             .execute(&mut *transaction)
             .await?;
 
+            if insert_result.rows_affected() == 0 {
+                continue;
+            }
+
             // 2. 更新提取请求状态
             sqlx::query(
                 r#"
@@ -874,106 +1362,517 @@ impl Database {
         Ok(count)
     }
 
-    // 根据request_id查询提取完成事件
-    pub async fn get_event_withdrawed_by_request_id(
+    // EventBankReward 事件相关方法
+    pub async fn save_bank_reward_event(
         &self,
-        request_id: &str,
-    ) -> Result<Option<models::EventWithdrawedEvent>> {
-        let result = sqlx::query_as::<_, models::EventWithdrawedEvent>(
-            "SELECT * FROM withdrawed_events WHERE request_id = $1"
+        event: &models::NewBankRewardEvent,
+        cursor: Option<&models::NewCursor>,
+    ) -> Result<Option<models::BankRewardEvent>> {
+        let mut transaction = self.pool.begin().await?;
+
+        let result = sqlx::query_as::<_, models::BankRewardEvent>(
+            r#"
+            INSERT INTO bank_reward_events (
+                bucky_bank_id, banks_count, reward_receiver, total_reward, bank_reward,
+                total_balance, bank_balance, tx_digest, event_seq, timestamp_ms
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (tx_digest, event_seq) DO NOTHING
+            RETURNING *
+            "#,
         )
-        .bind(request_id)
-        .fetch_optional(&self.pool)
+        .bind(&event.bucky_bank_id)
+        .bind(event.banks_count)
+        .bind(&event.reward_receiver)
+        .bind(event.total_reward)
+        .bind(event.bank_reward)
+        .bind(event.total_balance)
+        .bind(event.bank_balance)
+        .bind(&event.tx_digest)
+        .bind(event.event_seq)
+        .bind(event.timestamp_ms)
+        .fetch_optional(&mut *transaction)
         .await?;
 
+        Self::advance_cursor(&mut transaction, cursor).await?;
+
+        transaction.commit().await?;
         Ok(result)
     }
 
-    // 根据bucky_bank_id查询提取完成事件列表
-    pub async fn get_event_withdrawed_by_bank_id(
+    pub async fn save_bank_reward_events_batch(
         &self,
-        bucky_bank_id: &str,
-        limit: Option<i64>,
-        offset: Option<i64>,
-    ) -> Result<Vec<models::EventWithdrawedEvent>> {
-        let query = r#"
-            SELECT * FROM withdrawed_events 
-            WHERE bucky_bank_id = $1 
-            ORDER BY created_at_ms DESC 
-            LIMIT $2 OFFSET $3
-        "#;
-
-        let result = sqlx::query_as::<_, models::EventWithdrawedEvent>(query)
-            .bind(bucky_bank_id)
-            .bind(limit.unwrap_or(50))
-            .bind(offset.unwrap_or(0))
-            .fetch_all(&self.pool)
+        events: &[models::NewBankRewardEvent],
+    ) -> Result<u64> {
+        let mut transaction = self.pool.begin().await?;
+
+        let mut count = 0;
+        for event in events {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO bank_reward_events (
+                    bucky_bank_id, banks_count, reward_receiver, total_reward, bank_reward,
+                    total_balance, bank_balance, tx_digest, event_seq, timestamp_ms
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                ON CONFLICT (tx_digest, event_seq) DO NOTHING
+                "#,
+            )
+            .bind(&event.bucky_bank_id)
+            .bind(event.banks_count)
+            .bind(&event.reward_receiver)
+            .bind(event.total_reward)
+            .bind(event.bank_reward)
+            .bind(event.total_balance)
+            .bind(event.bank_balance)
+            .bind(&event.tx_digest)
+            .bind(event.event_seq)
+            .bind(event.timestamp_ms)
+            .execute(&mut *transaction)
+            .await?;
+
+            count += result.rows_affected();
+        }
+
+        transaction.commit().await?;
+        Ok(count)
+    }
+
+    // EventClaimReward 事件相关方法
+    pub async fn save_reward_claim_event(
+        &self,
+        event: &models::NewRewardClaimEvent,
+        cursor: Option<&models::NewCursor>,
+    ) -> Result<Option<models::RewardClaimEvent>> {
+        let mut transaction = self.pool.begin().await?;
+
+        let result = sqlx::query_as::<_, models::RewardClaimEvent>(
+            r#"
+            INSERT INTO reward_claim_events (
+                bucky_bank_id, reward_receiver, reward_amount, created_at_ms, tx_digest, event_seq
+            ) VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (tx_digest, event_seq) DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(&event.bucky_bank_id)
+        .bind(&event.reward_receiver)
+        .bind(event.reward_amount)
+        .bind(event.created_at_ms)
+        .bind(&event.tx_digest)
+        .bind(event.event_seq)
+        .fetch_optional(&mut *transaction)
+        .await?;
+
+        Self::advance_cursor(&mut transaction, cursor).await?;
+
+        transaction.commit().await?;
+        Ok(result)
+    }
+
+    pub async fn save_reward_claim_events_batch(
+        &self,
+        events: &[models::NewRewardClaimEvent],
+    ) -> Result<u64> {
+        let mut transaction = self.pool.begin().await?;
+
+        let mut count = 0;
+        for event in events {
+            let result = sqlx::query(
+                r#"
+                INSERT INTO reward_claim_events (
+                    bucky_bank_id, reward_receiver, reward_amount, created_at_ms, tx_digest, event_seq
+                ) VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (tx_digest, event_seq) DO NOTHING
+                "#,
+            )
+            .bind(&event.bucky_bank_id)
+            .bind(&event.reward_receiver)
+            .bind(event.reward_amount)
+            .bind(event.created_at_ms)
+            .bind(&event.tx_digest)
+            .bind(event.event_seq)
+            .execute(&mut *transaction)
             .await?;
 
+            count += result.rows_affected();
+        }
+
+        transaction.commit().await?;
+        Ok(count)
+    }
+
+    // 事件处理失败（死信）相关方法
+    // 记录处理失败的事件并推进游标（回填时不移动游标），同一事件再次失败时累加处理次数
+    pub async fn save_event_failure(
+        &self,
+        failure: &models::NewEventFailure,
+        cursor: Option<&models::NewCursor>,
+    ) -> Result<models::EventFailure> {
+        let mut transaction = self.pool.begin().await?;
+
+        let result = sqlx::query_as::<_, models::EventFailure>(
+            r#"
+            INSERT INTO event_failures (
+                tx_digest, event_seq, event_type, parsed_json, error, attempts, timestamp_ms
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (tx_digest, event_seq) DO UPDATE SET
+                error = EXCLUDED.error,
+                attempts = event_failures.attempts + EXCLUDED.attempts,
+                updated_at = CURRENT_TIMESTAMP
+            RETURNING *
+            "#,
+        )
+        .bind(&failure.tx_digest)
+        .bind(failure.event_seq)
+        .bind(&failure.event_type)
+        .bind(&failure.parsed_json)
+        .bind(&failure.error)
+        .bind(failure.attempts)
+        .bind(failure.timestamp_ms)
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        Self::advance_cursor(&mut transaction, cursor).await?;
+
+        transaction.commit().await?;
         Ok(result)
     }
 
-    // 根据withdrawer查询提取完成事件列表
-    pub async fn get_event_withdrawed_by_withdrawer(
+    // 查询失败事件列表，resolved 为空时返回全部
+    pub async fn get_event_failures(
+        &self,
+        resolved: Option<bool>,
+        page: &PageRequest,
+    ) -> Result<Page<models::EventFailure>> {
+        self.fetch_admin_page(
+            "event_failures",
+            |query| match resolved {
+                Some(true) => {
+                    query.push(" AND resolved_at IS NOT NULL");
+                }
+                Some(false) => {
+                    query.push(" AND resolved_at IS NULL");
+                }
+                None => {}
+            },
+            page,
+        )
+        .await
+    }
+
+    // 按写入顺序取出尚未重放成功的失败事件，供批量重放使用
+    pub async fn get_unresolved_event_failures(
+        &self,
+        limit: i64,
+    ) -> Result<Vec<models::EventFailure>> {
+        let result = sqlx::query_as::<_, models::EventFailure>(
+            r#"
+            SELECT * FROM event_failures
+            WHERE resolved_at IS NULL
+            ORDER BY created_at ASC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    pub async fn get_event_failure_by_id(
+        &self,
+        id: uuid::Uuid,
+    ) -> Result<Option<models::EventFailure>> {
+        let result = sqlx::query_as::<_, models::EventFailure>(
+            "SELECT * FROM event_failures WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    // 标记失败事件已重放成功
+    pub async fn resolve_event_failure(&self, id: uuid::Uuid) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE event_failures
+            SET resolved_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND resolved_at IS NULL
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // 记录一次失败的重放，累加处理次数并更新错误信息
+    pub async fn record_event_failure_attempt(&self, id: uuid::Uuid, error: &str) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE event_failures
+            SET attempts = attempts + 1, error = $2, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // 创建 webhook 订阅
+    pub async fn create_webhook_subscription(
         &self,
-        withdrawer: &str,
-        limit: Option<i64>,
-        offset: Option<i64>,
-    ) -> Result<Vec<models::EventWithdrawedEvent>> {
-        let query = r#"
-            SELECT * FROM withdrawed_events 
-            WHERE withdrawer = $1 
-            ORDER BY created_at_ms DESC 
-            LIMIT $2 OFFSET $3
-        "#;
-
-        let result = sqlx::query_as::<_, models::EventWithdrawedEvent>(query)
-            .bind(withdrawer)
-            .bind(limit.unwrap_or(50))
-            .bind(offset.unwrap_or(0))
-            .fetch_all(&self.pool)
+        subscription: &models::NewWebhookSubscription,
+        secret: &str,
+    ) -> Result<models::WebhookSubscription> {
+        let result = sqlx::query_as::<_, models::WebhookSubscription>(
+            r#"
+            INSERT INTO webhook_subscriptions (url, secret, event_types, bucky_bank_id)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+        )
+        .bind(&subscription.url)
+        .bind(secret)
+        .bind(&subscription.event_types)
+        .bind(&subscription.bucky_bank_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    pub async fn get_webhook_subscriptions(&self) -> Result<Vec<models::WebhookSubscription>> {
+        let result = sqlx::query_as::<_, models::WebhookSubscription>(
+            "SELECT * FROM webhook_subscriptions ORDER BY created_at",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    // 删除订阅，投递记录随之级联删除
+    pub async fn delete_webhook_subscription(&self, id: uuid::Uuid) -> Result<bool> {
+        let result = sqlx::query("DELETE FROM webhook_subscriptions WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
             .await?;
 
+        Ok(result.rows_affected() > 0)
+    }
+
+    // 领取到期的待投递记录，并把 next_attempt_at 推迟 lease_seconds 秒，避免多个投递任务重复发送
+    pub async fn claim_webhook_deliveries(
+        &self,
+        limit: i64,
+        lease_seconds: i64,
+    ) -> Result<Vec<models::PendingWebhookDelivery>> {
+        let result = sqlx::query_as::<_, models::PendingWebhookDelivery>(
+            r#"
+            WITH due AS (
+                SELECT id FROM webhook_deliveries
+                WHERE status = 'Pending' AND next_attempt_at <= NOW()
+                ORDER BY next_attempt_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE webhook_deliveries d
+            SET next_attempt_at = NOW() + make_interval(secs => $2)
+            FROM due, webhook_subscriptions s
+            WHERE d.id = due.id AND s.id = d.subscription_id
+            RETURNING d.id, d.event_type, d.payload, d.attempts, s.url, s.secret
+            "#,
+        )
+        .bind(limit)
+        .bind(lease_seconds as f64)
+        .fetch_all(&self.pool)
+        .await?;
+
         Ok(result)
     }
 
-    // 获取提取完成事件统计信息
-    pub async fn get_event_withdrawed_stats(
+    // 记录一次投递结果；retry_after_seconds 为空表示不再重试
+    pub async fn record_webhook_attempt(
         &self,
-        bucky_bank_id: Option<&str>,
-    ) -> Result<serde_json::Value> {
-        let query = if bucky_bank_id.is_some() {
+        id: uuid::Uuid,
+        delivered: bool,
+        status_code: Option<i32>,
+        error: Option<&str>,
+        retry_after_seconds: Option<i64>,
+    ) -> Result<()> {
+        sqlx::query(
             r#"
-            SELECT 
-                COUNT(*) as total_count,
-                COALESCE(SUM(amount), 0) as total_amount,
-                COALESCE(AVG(amount), 0) as average_amount
-            FROM withdrawed_events 
-            WHERE bucky_bank_id = $1
-            "#
-        } else {
+            UPDATE webhook_deliveries
+            SET attempts = attempts + 1,
+                last_status_code = $3,
+                last_error = $4,
+                status = CASE
+                    WHEN $2 THEN 'Delivered'
+                    WHEN $5::BIGINT IS NULL THEN 'Failed'
+                    ELSE 'Pending'
+                END,
+                delivered_at = CASE WHEN $2 THEN NOW() ELSE delivered_at END,
+                next_attempt_at = CASE
+                    WHEN $5::BIGINT IS NULL THEN next_attempt_at
+                    ELSE NOW() + make_interval(secs => $5::BIGINT)
+                END
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(delivered)
+        .bind(status_code)
+        .bind(error)
+        .bind(retry_after_seconds)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn get_webhook_deliveries(
+        &self,
+        subscription_id: uuid::Uuid,
+        status: Option<&str>,
+        page: &PageRequest,
+    ) -> Result<Page<models::WebhookDelivery>> {
+        self.fetch_admin_page(
+            "webhook_deliveries",
+            |query| {
+                query.push(" AND subscription_id = ").push_bind(subscription_id);
+                if let Some(status) = status {
+                    query.push(" AND status = ").push_bind(status.to_string());
+                }
+            },
+            page,
+        )
+        .await
+    }
+
+    // 将失败的投递记录重新放回队列，立即重新投递
+    pub async fn retry_webhook_delivery(&self, id: uuid::Uuid) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'Pending', attempts = 0, next_attempt_at = NOW()
+            WHERE id = $1 AND status = 'Failed'
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // 创建管理接口 API Key，只保存摘要
+    pub async fn create_admin_api_key(&self, name: &str, key_hash: &str) -> Result<models::AdminApiKey> {
+        let result = sqlx::query_as::<_, models::AdminApiKey>(
             r#"
-            SELECT 
-                COUNT(*) as total_count,
-                COALESCE(SUM(amount), 0) as total_amount,
-                COALESCE(AVG(amount), 0) as average_amount
-            FROM withdrawed_events
-            "#
+            INSERT INTO admin_api_keys (name, key_hash)
+            VALUES ($1, $2)
+            RETURNING id, name, created_at, last_used_at, revoked_at
+            "#,
+        )
+        .bind(name)
+        .bind(key_hash)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    pub async fn revoke_admin_api_key(&self, name: &str) -> Result<bool> {
+        let result = sqlx::query(
+            "UPDATE admin_api_keys SET revoked_at = NOW() WHERE name = $1 AND revoked_at IS NULL",
+        )
+        .bind(name)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // 按摘要查找未吊销的 API Key，并记录最近使用时间
+    pub async fn authenticate_admin_api_key(&self, key_hash: &str) -> Result<Option<models::AdminApiKey>> {
+        let result = sqlx::query_as::<_, models::AdminApiKey>(
+            r#"
+            UPDATE admin_api_keys
+            SET last_used_at = NOW()
+            WHERE key_hash = $1 AND revoked_at IS NULL
+            RETURNING id, name, created_at, last_used_at, revoked_at
+            "#,
+        )
+        .bind(key_hash)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(result)
+    }
+
+    // 管理接口列表按 (created_at, id) 倒序分页，filters 以 " AND ..." 追加过滤条件；
+    // 多取一条用于判断是否还有下一页，游标中的排序时间为 created_at 的微秒时间戳
+    async fn fetch_admin_page<T>(
+        &self,
+        table: &str,
+        filters: impl Fn(&mut QueryBuilder<'_, Postgres>),
+        page: &PageRequest,
+    ) -> Result<Page<T>>
+    where
+        T: for<'r> FromRow<'r, PgRow> + Keyset + Send + Unpin,
+    {
+        let total = if page.include_total {
+            let mut count_query =
+                QueryBuilder::<Postgres>::new(format!("SELECT COUNT(*) FROM {} WHERE TRUE", table));
+            filters(&mut count_query);
+            Some(count_query.build_query_scalar().fetch_one(&self.pool).await?)
+        } else {
+            None
         };
 
-        let mut query_builder = sqlx::query(query);
-        if let Some(bank_id) = bucky_bank_id {
-            query_builder = query_builder.bind(bank_id);
+        let mut data_query =
+            QueryBuilder::<Postgres>::new(format!("SELECT * FROM {} WHERE TRUE", table));
+        filters(&mut data_query);
+        if let Some(after) = &page.after {
+            let created_at = DateTime::from_timestamp_micros(after.sort_key)
+                .ok_or_else(|| anyhow::anyhow!("Cursor timestamp out of range: {}", after.sort_key))?;
+            let CursorId::Uuid(id) = after.id else {
+                anyhow::bail!("Cursor id must be a UUID: {}", after.id);
+            };
+            data_query
+                .push(" AND (created_at, id) < (")
+                .push_bind(created_at)
+                .push(", ")
+                .push_bind(id)
+                .push(")");
         }
+        data_query
+            .push(" ORDER BY created_at DESC, id DESC LIMIT ")
+            .push_bind(page.limit + 1);
+        if page.after.is_none() {
+            data_query.push(" OFFSET ").push_bind(page.offset);
+        }
+        let mut items = data_query.build_query_as::<T>().fetch_all(&self.pool).await?;
 
-        let row = query_builder.fetch_one(&self.pool).await?;
-
-        let stats = serde_json::json!({
-            "total_count": row.get::<i64, _>("total_count"),
-            "total_amount": row.get::<i64, _>("total_amount"),
-            "average_amount": row.get::<f64, _>("average_amount")
-        });
+        let next_cursor = if items.len() as i64 > page.limit {
+            items.truncate(page.limit as usize);
+            items.last().map(|item| item.page_cursor().encode())
+        } else {
+            None
+        };
 
-        Ok(stats)
+        Ok(Page {
+            items,
+            next_cursor,
+            total,
+        })
     }
 }
@@ -0,0 +1,662 @@
+//! 与合约 `bucky_bank::bucky_bank` 中事件结构体一一对应的类型
+//!
+//! 同一组结构体既可以从 RPC 返回的 `parsed_json` 解析，也可以从事件的 BCS 字节解析：
+//! JSON 中 u64 为十进制字符串、ID 和地址为 `0x` 开头的十六进制字符串、枚举为 `{"variant": ..}`，
+//! BCS 中则分别为小端整数、32 字节和变体序号，字段解析函数通过 `is_human_readable` 区分两种格式。
+//! BCS 按字段顺序解码，字段顺序必须与合约中的定义保持一致。
+
+use crate::database::models::WithdrawalStatus;
+use anyhow::{Result, anyhow};
+use serde::de::{self, DeserializeOwned, IntoDeserializer};
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+
+/// 合约事件，名称为链上事件结构体名称
+pub trait MoveEvent: DeserializeOwned {
+    const NAME: &'static str;
+
+    fn from_json(value: &serde_json::Value) -> Result<Self> {
+        Self::deserialize(value).map_err(|e| anyhow!("Invalid {} event JSON: {}", Self::NAME, e))
+    }
+
+    fn from_bcs(bytes: &[u8]) -> Result<Self> {
+        bcs::from_bytes(bytes).map_err(|e| anyhow!("Invalid {} event BCS: {}", Self::NAME, e))
+    }
+}
+
+/// u64：JSON 中为十进制字符串（兼容数字），BCS 中为 8 字节小端整数
+pub fn move_u64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    struct U64Visitor;
+
+    impl de::Visitor<'_> for U64Visitor {
+        type Value = u64;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a u64 as a decimal string")
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<u64, E> {
+            Ok(value)
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<u64, E> {
+            value
+                .parse()
+                .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+        }
+    }
+
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(U64Visitor)
+    } else {
+        u64::deserialize(deserializer)
+    }
+}
+
+/// `address` 和 `ID`：JSON 中为十六进制字符串（原样保留），BCS 中为 32 字节
+pub fn move_address<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    if deserializer.is_human_readable() {
+        String::deserialize(deserializer)
+    } else {
+        let bytes = <[u8; 32]>::deserialize(deserializer)?;
+        Ok(format!("0x{}", hex::encode(bytes)))
+    }
+}
+
+/// 无字段的 Move 枚举：JSON 中为 `{"variant": "Pending", "fields": {}}`（兼容直接写变体名），
+/// BCS 中为变体序号，Rust 枚举的变体顺序需与合约一致
+pub fn move_enum<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum MoveEnumJson {
+        Variant { variant: String },
+        Name(String),
+    }
+
+    if !deserializer.is_human_readable() {
+        return T::deserialize(deserializer);
+    }
+
+    let name = match MoveEnumJson::deserialize(deserializer)? {
+        MoveEnumJson::Variant { variant } => variant,
+        MoveEnumJson::Name(name) => name,
+    };
+    T::deserialize(name.into_deserializer())
+}
+
+/// 存钱罐创建事件
+#[derive(Debug, Clone, Deserialize)]
+pub struct BuckyBankCreated {
+    #[serde(deserialize_with = "move_address")]
+    pub bucky_bank_id: String,
+    pub name: String,
+    #[serde(deserialize_with = "move_address")]
+    pub parent: String,
+    #[serde(deserialize_with = "move_address")]
+    pub child: String,
+    #[serde(deserialize_with = "move_u64")]
+    pub target_amount: u64,
+    #[serde(deserialize_with = "move_u64")]
+    pub current_balance: u64,
+    #[serde(deserialize_with = "move_u64")]
+    pub created_at_ms: u64,
+    #[serde(deserialize_with = "move_u64")]
+    pub deadline_ms: u64,
+    #[serde(deserialize_with = "move_u64")]
+    pub duration_days: u64,
+}
+
+impl MoveEvent for BuckyBankCreated {
+    const NAME: &'static str = "BuckyBankCreated";
+}
+
+/// 存款事件
+#[derive(Debug, Clone, Deserialize)]
+pub struct DepositMade {
+    #[serde(deserialize_with = "move_address")]
+    pub bucky_bank_id: String,
+    #[serde(deserialize_with = "move_u64")]
+    pub amount: u64,
+    #[serde(deserialize_with = "move_address")]
+    pub depositor: String,
+    #[serde(deserialize_with = "move_u64")]
+    pub created_at_ms: u64,
+}
+
+impl MoveEvent for DepositMade {
+    const NAME: &'static str = "DepositMade";
+}
+
+/// 存款奖励事件，本身不带时间字段
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventBankReward {
+    #[serde(deserialize_with = "move_address")]
+    pub bank_id: String,
+    #[serde(deserialize_with = "move_u64")]
+    pub banks_count: u64,
+    #[serde(deserialize_with = "move_address")]
+    pub reward_receiver: String,
+    #[serde(deserialize_with = "move_u64")]
+    pub total_reward: u64,
+    #[serde(deserialize_with = "move_u64")]
+    pub bank_reward: u64,
+    #[serde(deserialize_with = "move_u64")]
+    pub total_balance: u64,
+    #[serde(deserialize_with = "move_u64")]
+    pub bank_balance: u64,
+}
+
+impl MoveEvent for EventBankReward {
+    const NAME: &'static str = "EventBankReward";
+}
+
+/// 奖励领取事件
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventClaimReward {
+    #[serde(deserialize_with = "move_address")]
+    pub bank_id: String,
+    #[serde(deserialize_with = "move_address")]
+    pub reward_receiver: String,
+    #[serde(deserialize_with = "move_u64")]
+    pub reward_amount: u64,
+    #[serde(deserialize_with = "move_u64")]
+    pub created_at_ms: u64,
+}
+
+impl MoveEvent for EventClaimReward {
+    const NAME: &'static str = "EventClaimReward";
+}
+
+/// 提款请求事件
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventWithdrawalRequested {
+    #[serde(deserialize_with = "move_address")]
+    pub request_id: String,
+    #[serde(deserialize_with = "move_address")]
+    pub bucky_bank_id: String,
+    #[serde(deserialize_with = "move_u64")]
+    pub amount: u64,
+    #[serde(deserialize_with = "move_address")]
+    pub requester: String,
+    pub reason: String,
+    #[serde(deserialize_with = "move_enum")]
+    pub status: WithdrawalStatus,
+    // 早期的 JSON 记录中可能缺少该字段
+    #[serde(default, deserialize_with = "move_address")]
+    pub approved_by: String,
+    #[serde(deserialize_with = "move_u64")]
+    pub created_at_ms: u64,
+}
+
+impl MoveEvent for EventWithdrawalRequested {
+    const NAME: &'static str = "EventWithdrawalRequested";
+}
+
+/// 提款批准事件
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventWithdrawalApproved {
+    #[serde(deserialize_with = "move_address")]
+    pub request_id: String,
+    #[serde(deserialize_with = "move_address")]
+    pub bucky_bank_id: String,
+    #[serde(deserialize_with = "move_u64")]
+    pub amount: u64,
+    #[serde(deserialize_with = "move_address")]
+    pub requester: String,
+    #[serde(deserialize_with = "move_address")]
+    pub approved_by: String,
+    pub reason: String,
+    #[serde(deserialize_with = "move_u64")]
+    pub created_at_ms: u64,
+}
+
+impl MoveEvent for EventWithdrawalApproved {
+    const NAME: &'static str = "EventWithdrawalApproved";
+}
+
+/// 提款拒绝事件
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventWithdrawalRejected {
+    #[serde(deserialize_with = "move_address")]
+    pub request_id: String,
+    #[serde(deserialize_with = "move_address")]
+    pub bucky_bank_id: String,
+    #[serde(deserialize_with = "move_u64")]
+    pub amount: u64,
+    #[serde(deserialize_with = "move_address")]
+    pub requester: String,
+    #[serde(deserialize_with = "move_address")]
+    pub rejected_by: String,
+    pub reason: String,
+    #[serde(deserialize_with = "move_u64")]
+    pub created_at_ms: u64,
+}
+
+impl MoveEvent for EventWithdrawalRejected {
+    const NAME: &'static str = "EventWithdrawalRejected";
+}
+
+/// 提款完成事件
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventWithdrawed {
+    #[serde(deserialize_with = "move_address")]
+    pub request_id: String,
+    #[serde(deserialize_with = "move_address")]
+    pub bucky_bank_id: String,
+    #[serde(deserialize_with = "move_u64")]
+    pub amount: u64,
+    #[serde(deserialize_with = "move_u64")]
+    pub left_balance: u64,
+    #[serde(deserialize_with = "move_address")]
+    pub withdrawer: String,
+    #[serde(deserialize_with = "move_u64")]
+    pub created_at_ms: u64,
+}
+
+impl MoveEvent for EventWithdrawed {
+    const NAME: &'static str = "EventWithdrawed";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // 合约源码，测试数据按其中事件结构体的字段顺序编码 BCS，字段增删或调整顺序时测试随之失败
+    const CONTRACT_SOURCE: &str = include_str!("../../contract/bucky_bank/sources/bucky_bank.move");
+
+    const BUCKY_BANK_ID: &str =
+        "0xd7dd42e481af321a09533e727dd53c0ee58ef2257333e3471358254363c3c3de";
+    const REQUEST_ID: &str = "0x8048af51a7c16e4a0bd8555c8147e5e2e7a0b6ca59499d8ede96aea24db44b8d";
+    const PARENT: &str = "0x27b2306354b0537a9ac9eddb26e10f327d6ef660333902e313b1bcb4353c5d3f";
+    const CHILD: &str = "0x85044bd3ce9f5fc70f96820affa33e44ff5bdc105c3308fec1a17b0065d63471";
+
+    // 测试网上记录的 EventWithdrawalRequested 的 parsed_json
+    fn recorded_withdrawal_requested() -> serde_json::Value {
+        json!({
+            "amount": "3000000",
+            "approved_by": PARENT,
+            "bucky_bank_id": BUCKY_BANK_ID,
+            "created_at_ms": "1760166241636",
+            "reason": "I need it",
+            "request_id": REQUEST_ID,
+            "requester": PARENT,
+            "status": {"variant": "Pending", "fields": {}}
+        })
+    }
+
+    /// 事件字段的取值，按 Move 类型区分 JSON 和 BCS 编码
+    enum Field {
+        Address(&'static str),
+        U64(u64),
+        Str(&'static str),
+        /// 无字段枚举的变体名称
+        Variant(&'static str),
+    }
+
+    // 合约中 `public struct|enum <name>` 定义体内按顺序列出的字段名或变体名
+    fn contract_members(kind: &str, name: &str) -> Vec<&'static str> {
+        let header = format!("public {} {} has", kind, name);
+        let start = CONTRACT_SOURCE
+            .find(&header)
+            .unwrap_or_else(|| panic!("{} {} not found in contract", kind, name));
+        let body = &CONTRACT_SOURCE[start..];
+        let body = &body[body.find('{').unwrap() + 1..body.find('}').unwrap()];
+
+        body.lines()
+            .map(|line| line.split("//").next().unwrap().trim())
+            .filter(|line| !line.is_empty())
+            .map(|line| line.split([':', ',']).next().unwrap().trim())
+            .collect()
+    }
+
+    /// 按合约字段顺序生成事件的 parsed_json 和 BCS 字节，字段必须与合约完全一致
+    fn fixture(name: &str, fields: &[(&str, Field)]) -> (serde_json::Value, Vec<u8>) {
+        let order = contract_members("struct", name);
+        let mut names: Vec<&str> = fields.iter().map(|(field, _)| *field).collect();
+        names.sort_unstable();
+        let mut expected = order.clone();
+        expected.sort_unstable();
+        assert_eq!(names, expected, "fields of {}", name);
+
+        let statuses = contract_members("enum", "WithdrawalStatus");
+        let mut json = serde_json::Map::new();
+        let mut bytes = Vec::new();
+        for field in order {
+            let (_, value) = fields.iter().find(|(name, _)| *name == field).unwrap();
+            let (json_value, bcs_value) = match value {
+                Field::Address(address) => (json!(address), address_bytes(address).to_vec()),
+                Field::U64(value) => (json!(value.to_string()), bcs::to_bytes(value).unwrap()),
+                Field::Str(value) => (json!(value), bcs::to_bytes(value).unwrap()),
+                Field::Variant(variant) => {
+                    let index = statuses
+                        .iter()
+                        .position(|status| status == variant)
+                        .unwrap();
+                    (
+                        json!({"variant": variant, "fields": {}}),
+                        bcs::to_bytes(&(index as u8)).unwrap(),
+                    )
+                }
+            };
+            json.insert(field.to_string(), json_value);
+            bytes.extend(bcs_value);
+        }
+        (serde_json::Value::Object(json), bytes)
+    }
+
+    // BCS 中的 address / ID
+    fn address_bytes(address: &str) -> [u8; 32] {
+        hex::decode(address.trim_start_matches("0x"))
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    fn withdrawal_requested_fixture() -> (serde_json::Value, Vec<u8>) {
+        fixture(
+            "EventWithdrawalRequested",
+            &[
+                ("request_id", Field::Address(REQUEST_ID)),
+                ("bucky_bank_id", Field::Address(BUCKY_BANK_ID)),
+                ("amount", Field::U64(3000000)),
+                ("requester", Field::Address(PARENT)),
+                ("reason", Field::Str("I need it")),
+                ("status", Field::Variant("Pending")),
+                ("approved_by", Field::Address(PARENT)),
+                ("created_at_ms", Field::U64(1760166241636)),
+            ],
+        )
+    }
+
+    #[test]
+    fn fixture_json_matches_recorded_event() {
+        let (json, _) = withdrawal_requested_fixture();
+        assert_eq!(json, recorded_withdrawal_requested());
+    }
+
+    #[test]
+    fn withdrawal_status_variants_match_contract() {
+        let statuses = contract_members("enum", "WithdrawalStatus");
+        for (index, status) in statuses.iter().enumerate() {
+            let bytes = bcs::to_bytes(&(index as u8)).unwrap();
+            let decoded: WithdrawalStatus = bcs::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded.to_string(), *status);
+        }
+    }
+
+    #[test]
+    fn decodes_bucky_bank_created() {
+        let (json, bytes) = fixture(
+            "BuckyBankCreated",
+            &[
+                ("bucky_bank_id", Field::Address(BUCKY_BANK_ID)),
+                ("name", Field::Str("Bike")),
+                ("parent", Field::Address(PARENT)),
+                ("child", Field::Address(CHILD)),
+                ("target_amount", Field::U64(10000000)),
+                ("current_balance", Field::U64(0)),
+                ("created_at_ms", Field::U64(1760166000000)),
+                ("deadline_ms", Field::U64(1762758000000)),
+                ("duration_days", Field::U64(30)),
+            ],
+        );
+
+        for event in [
+            BuckyBankCreated::from_json(&json).unwrap(),
+            BuckyBankCreated::from_bcs(&bytes).unwrap(),
+        ] {
+            assert_eq!(event.bucky_bank_id, BUCKY_BANK_ID);
+            assert_eq!(event.name, "Bike");
+            assert_eq!(event.parent, PARENT);
+            assert_eq!(event.child, CHILD);
+            assert_eq!(event.target_amount, 10000000);
+            assert_eq!(event.current_balance, 0);
+            assert_eq!(event.created_at_ms, 1760166000000);
+            assert_eq!(event.deadline_ms, 1762758000000);
+            assert_eq!(event.duration_days, 30);
+        }
+    }
+
+    #[test]
+    fn decodes_deposit_made() {
+        let (json, bytes) = fixture(
+            "DepositMade",
+            &[
+                ("bucky_bank_id", Field::Address(BUCKY_BANK_ID)),
+                ("amount", Field::U64(3000000)),
+                ("depositor", Field::Address(PARENT)),
+                ("created_at_ms", Field::U64(1760166100000)),
+            ],
+        );
+
+        for event in [
+            DepositMade::from_json(&json).unwrap(),
+            DepositMade::from_bcs(&bytes).unwrap(),
+        ] {
+            assert_eq!(event.bucky_bank_id, BUCKY_BANK_ID);
+            assert_eq!(event.amount, 3000000);
+            assert_eq!(event.depositor, PARENT);
+            assert_eq!(event.created_at_ms, 1760166100000);
+        }
+    }
+
+    #[test]
+    fn decodes_bank_reward() {
+        let (json, bytes) = fixture(
+            "EventBankReward",
+            &[
+                ("bank_id", Field::Address(BUCKY_BANK_ID)),
+                ("banks_count", Field::U64(2)),
+                ("reward_receiver", Field::Address(CHILD)),
+                ("total_reward", Field::U64(1000)),
+                ("bank_reward", Field::U64(600)),
+                ("total_balance", Field::U64(5000000)),
+                ("bank_balance", Field::U64(3000000)),
+            ],
+        );
+
+        for event in [
+            EventBankReward::from_json(&json).unwrap(),
+            EventBankReward::from_bcs(&bytes).unwrap(),
+        ] {
+            assert_eq!(event.bank_id, BUCKY_BANK_ID);
+            assert_eq!(event.banks_count, 2);
+            assert_eq!(event.reward_receiver, CHILD);
+            assert_eq!(event.total_reward, 1000);
+            assert_eq!(event.bank_reward, 600);
+            assert_eq!(event.total_balance, 5000000);
+            assert_eq!(event.bank_balance, 3000000);
+        }
+    }
+
+    #[test]
+    fn decodes_claim_reward() {
+        let (json, bytes) = fixture(
+            "EventClaimReward",
+            &[
+                ("bank_id", Field::Address(BUCKY_BANK_ID)),
+                ("reward_receiver", Field::Address(CHILD)),
+                ("reward_amount", Field::U64(600)),
+                ("created_at_ms", Field::U64(1760166300000)),
+            ],
+        );
+
+        for event in [
+            EventClaimReward::from_json(&json).unwrap(),
+            EventClaimReward::from_bcs(&bytes).unwrap(),
+        ] {
+            assert_eq!(event.bank_id, BUCKY_BANK_ID);
+            assert_eq!(event.reward_receiver, CHILD);
+            assert_eq!(event.reward_amount, 600);
+            assert_eq!(event.created_at_ms, 1760166300000);
+        }
+    }
+
+    #[test]
+    fn decodes_withdrawal_requested() {
+        let (_, bytes) = withdrawal_requested_fixture();
+
+        for event in [
+            EventWithdrawalRequested::from_json(&recorded_withdrawal_requested()).unwrap(),
+            EventWithdrawalRequested::from_bcs(&bytes).unwrap(),
+        ] {
+            assert_eq!(event.request_id, REQUEST_ID);
+            assert_eq!(event.bucky_bank_id, BUCKY_BANK_ID);
+            assert_eq!(event.amount, 3000000);
+            assert_eq!(event.requester, PARENT);
+            assert_eq!(event.reason, "I need it");
+            assert!(matches!(event.status, WithdrawalStatus::Pending));
+            assert_eq!(event.approved_by, PARENT);
+            assert_eq!(event.created_at_ms, 1760166241636);
+        }
+    }
+
+    #[test]
+    fn withdrawal_requested_without_approver_defaults_to_empty() {
+        let mut json = recorded_withdrawal_requested();
+        json.as_object_mut().unwrap().remove("approved_by");
+
+        let event = EventWithdrawalRequested::from_json(&json).unwrap();
+        assert_eq!(event.approved_by, "");
+        assert_eq!(event.request_id, REQUEST_ID);
+    }
+
+    #[test]
+    fn decodes_withdrawal_approved() {
+        let (json, bytes) = fixture(
+            "EventWithdrawalApproved",
+            &[
+                ("request_id", Field::Address(REQUEST_ID)),
+                ("bucky_bank_id", Field::Address(BUCKY_BANK_ID)),
+                ("amount", Field::U64(3000000)),
+                ("requester", Field::Address(CHILD)),
+                ("approved_by", Field::Address(PARENT)),
+                ("reason", Field::Str("I need it")),
+                ("created_at_ms", Field::U64(1760166400000)),
+            ],
+        );
+
+        for event in [
+            EventWithdrawalApproved::from_json(&json).unwrap(),
+            EventWithdrawalApproved::from_bcs(&bytes).unwrap(),
+        ] {
+            assert_eq!(event.request_id, REQUEST_ID);
+            assert_eq!(event.bucky_bank_id, BUCKY_BANK_ID);
+            assert_eq!(event.amount, 3000000);
+            assert_eq!(event.requester, CHILD);
+            assert_eq!(event.approved_by, PARENT);
+            assert_eq!(event.reason, "I need it");
+            assert_eq!(event.created_at_ms, 1760166400000);
+        }
+    }
+
+    #[test]
+    fn decodes_withdrawal_rejected() {
+        let (json, bytes) = fixture(
+            "EventWithdrawalRejected",
+            &[
+                ("request_id", Field::Address(REQUEST_ID)),
+                ("bucky_bank_id", Field::Address(BUCKY_BANK_ID)),
+                ("amount", Field::U64(3000000)),
+                ("requester", Field::Address(CHILD)),
+                ("rejected_by", Field::Address(PARENT)),
+                ("reason", Field::Str("Not yet")),
+                ("created_at_ms", Field::U64(1760166400000)),
+            ],
+        );
+
+        for event in [
+            EventWithdrawalRejected::from_json(&json).unwrap(),
+            EventWithdrawalRejected::from_bcs(&bytes).unwrap(),
+        ] {
+            assert_eq!(event.request_id, REQUEST_ID);
+            assert_eq!(event.bucky_bank_id, BUCKY_BANK_ID);
+            assert_eq!(event.amount, 3000000);
+            assert_eq!(event.requester, CHILD);
+            assert_eq!(event.rejected_by, PARENT);
+            assert_eq!(event.reason, "Not yet");
+            assert_eq!(event.created_at_ms, 1760166400000);
+        }
+    }
+
+    #[test]
+    fn decodes_withdrawed() {
+        let (json, bytes) = fixture(
+            "EventWithdrawed",
+            &[
+                ("request_id", Field::Address(REQUEST_ID)),
+                ("bucky_bank_id", Field::Address(BUCKY_BANK_ID)),
+                ("amount", Field::U64(3000000)),
+                ("left_balance", Field::U64(0)),
+                ("withdrawer", Field::Address(CHILD)),
+                ("created_at_ms", Field::U64(1760166500000)),
+            ],
+        );
+
+        for event in [
+            EventWithdrawed::from_json(&json).unwrap(),
+            EventWithdrawed::from_bcs(&bytes).unwrap(),
+        ] {
+            assert_eq!(event.request_id, REQUEST_ID);
+            assert_eq!(event.bucky_bank_id, BUCKY_BANK_ID);
+            assert_eq!(event.amount, 3000000);
+            assert_eq!(event.left_balance, 0);
+            assert_eq!(event.withdrawer, CHILD);
+            assert_eq!(event.created_at_ms, 1760166500000);
+        }
+    }
+
+    #[test]
+    fn accepts_numbers_and_bare_variant_names_in_json() {
+        let mut json = recorded_withdrawal_requested();
+        json["amount"] = json!(3000000);
+        json["status"] = json!("Approved");
+
+        let event = EventWithdrawalRequested::from_json(&json).unwrap();
+        assert_eq!(event.amount, 3000000);
+        assert!(matches!(event.status, WithdrawalStatus::Approved));
+    }
+
+    #[test]
+    fn rejects_malformed_fields() {
+        let mut json = recorded_withdrawal_requested();
+        json["amount"] = json!("3,000,000");
+        let error = EventWithdrawalRequested::from_json(&json).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("Invalid EventWithdrawalRequested event JSON"),
+            "{}",
+            error
+        );
+
+        let mut json = recorded_withdrawal_requested();
+        json["status"] = json!({"variant": "Unknown", "fields": {}});
+        assert!(EventWithdrawalRequested::from_json(&json).is_err());
+
+        // BCS 中缺少最后一个字段
+        let (_, mut bytes) = fixture(
+            "DepositMade",
+            &[
+                ("bucky_bank_id", Field::Address(BUCKY_BANK_ID)),
+                ("amount", Field::U64(3000000)),
+                ("depositor", Field::Address(PARENT)),
+                ("created_at_ms", Field::U64(1760166100000)),
+            ],
+        );
+        bytes.truncate(bytes.len() - 8);
+        let error = DepositMade::from_bcs(&bytes).unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("Invalid DepositMade event BCS"),
+            "{}",
+            error
+        );
+    }
+}
@@ -1,352 +1,493 @@
-use crate::database::{Database, models::{WithdrawalRequestEvent, EventWithdrawedEvent, WithdrawalStatus}};
+use crate::database::{models::{EventFailure, ReconcileReport, WithdrawalStatus, WebhookSubscription, NewWebhookSubscription, WebhookDelivery, AdminApiKey, WithdrawalStatusCorrection, NewWithdrawalStatusCorrection, CorrectionOutcome}};
 use crate::health::HealthState;
+use crate::indexer::{BuckyBankIndexer, ReplayResult};
+use crate::verifier::{VerificationResult, VerifierStats};
+use crate::webhook::{DELIVERY_STATUSES, WEBHOOK_EVENT_TYPES};
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{Extension, Path, Query, State},
     response::Json,
 };
+use bucky_bank_api::{ApiError, ApiResponse, auth, models::WITHDRAWAL_STATUSES, pagination::PageRequest};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 #[derive(Debug, Deserialize)]
-pub struct PaginationParams {
+pub struct EventFailureQuery {
+    pub resolved: Option<bool>,
+    pub page: Option<i64>,
     pub limit: Option<i64>,
-    pub offset: Option<i64>,
+    pub cursor: Option<String>,
+    pub include_total: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
-pub struct WithdrawalStatusQuery {
+pub struct ReplayQuery {
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReconcileQuery {
+    pub bucky_bank_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WebhookDeliveryQuery {
     pub status: Option<String>,
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    pub include_total: Option<bool>,
 }
 
+// 创建订阅的响应，签名密钥只在创建时返回一次
 #[derive(Debug, Serialize)]
-pub struct ApiResponse<T> {
-    pub success: bool,
-    pub data: Option<T>,
-    pub message: Option<String>,
+pub struct CreatedWebhookSubscription {
+    #[serde(flatten)]
+    pub subscription: WebhookSubscription,
+    pub secret: String,
 }
 
-impl<T> ApiResponse<T> {
-    pub fn success(data: T) -> Self {
-        Self {
-            success: true,
-            data: Some(data),
-            message: None,
+#[derive(Debug, Deserialize)]
+pub struct CorrectionQuery {
+    pub request_id: Option<String>,
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    pub include_total: Option<bool>,
+}
+
+// 人工修正提取请求状态（需管理接口 API Key），修正前后状态、原因和操作者写入审计表
+pub async fn correct_withdrawal_request_status(
+    State(state): State<HealthState>,
+    Extension(admin): Extension<AdminApiKey>,
+    Path(request_id): Path<String>,
+    Json(correction): Json<NewWithdrawalStatusCorrection>,
+) -> Result<Json<ApiResponse<WithdrawalStatusCorrection>>, ApiError> {
+    for (field, status) in [
+        ("status", &correction.status),
+        ("expected_status", &correction.expected_status),
+    ] {
+        if status.parse::<WithdrawalStatus>().is_err() {
+            return Err(ApiError::validation_with_details(
+                format!("Invalid {}: {}", field, status),
+                serde_json::json!({ "field": field, "allowed": WITHDRAWAL_STATUSES }),
+            ));
         }
     }
+    if correction.status == correction.expected_status {
+        return Err(ApiError::validation("status must differ from expected_status"));
+    }
+    if correction.reason.trim().is_empty() {
+        return Err(ApiError::validation_with_details(
+            "A reason is required for manual corrections",
+            serde_json::json!({ "field": "reason" }),
+        ));
+    }
 
-    pub fn error(message: String) -> Self {
-        Self {
-            success: false,
-            data: None,
-            message: Some(message),
+    match state
+        .db
+        .correct_withdrawal_request_status(&request_id, &correction, &admin.name)
+        .await
+    {
+        Ok(CorrectionOutcome::Corrected(record)) => {
+            tracing::warn!(
+                "Withdrawal request {} manually corrected from {} to {} by {}: {}",
+                request_id,
+                record.previous_status,
+                record.corrected_status,
+                record.corrected_by,
+                record.reason
+            );
+            Ok(Json(ApiResponse::success(record)))
+        }
+        Ok(CorrectionOutcome::NotFound) => Err(ApiError::not_found(format!(
+            "Withdrawal request {} not found",
+            request_id
+        ))),
+        Ok(CorrectionOutcome::StatusMismatch(current)) => Err(ApiError::conflict(format!(
+            "Withdrawal request {} is {}, not {}",
+            request_id, current, correction.expected_status
+        ))),
+        Err(e) => {
+            tracing::error!("Failed to correct withdrawal request status: {}", e);
+            Err(ApiError::database("Failed to correct withdrawal request status", e))
         }
     }
 }
 
-#[derive(Debug, Serialize)]
-pub struct WithdrawalRequestResponse {
-    pub id: i32,
-    pub request_id: String,
-    pub bucky_bank_id: String,
-    pub amount: i64,
-    pub requester: String,
-    pub reason: String,
-    pub status: String,
-    pub approved_by: Option<String>,
-    pub created_at_ms: i64,
-    pub audit_at_ms: Option<i64>,        // 审批时间
-    pub indexed_at: Option<chrono::DateTime<chrono::Utc>>,
-}
-
-impl From<WithdrawalRequestEvent> for WithdrawalRequestResponse {
-    fn from(event: WithdrawalRequestEvent) -> Self {
-        Self {
-            id: event.id,
-            request_id: event.request_id,
-            bucky_bank_id: event.bucky_bank_id,
-            amount: event.amount,
-            requester: event.requester,
-            reason: event.reason,
-            status: event.status,
-            approved_by: event.approved_by,
-            created_at_ms: event.created_at_ms,
-            audit_at_ms: event.audit_at_ms, // 添加审批时间
-            indexed_at: event.indexed_at,
+// 获取提取请求状态人工修正记录，可按 request_id 过滤
+pub async fn get_withdrawal_status_corrections(
+    State(state): State<HealthState>,
+    Query(query): Query<CorrectionQuery>,
+) -> Result<Json<ApiResponse<Vec<WithdrawalStatusCorrection>>>, ApiError> {
+    let page = PageRequest::new::<WithdrawalStatusCorrection>(
+        query.page,
+        query.limit,
+        query.cursor.as_deref(),
+        query.include_total.unwrap_or(false),
+    )?;
+    match state
+        .db
+        .get_withdrawal_status_corrections(query.request_id.as_deref(), &page)
+        .await
+    {
+        Ok(corrections) => Ok(Json(ApiResponse::page(corrections))),
+        Err(e) => {
+            tracing::error!("Failed to get withdrawal status corrections: {}", e);
+            Err(ApiError::database("Failed to get withdrawal status corrections", e))
         }
     }
 }
 
-#[derive(Debug, Serialize)]
-pub struct EventWithdrawedResponse {
-    pub id: uuid::Uuid,
-    pub request_id: String,
-    pub bucky_bank_id: String,
-    pub amount: i64,
-    pub left_balance: i64,
-    pub withdrawer: String,
-    pub created_at_ms: i64,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-}
+// 处理失败事件（死信）相关API
 
-impl From<EventWithdrawedEvent> for EventWithdrawedResponse {
-    fn from(event: EventWithdrawedEvent) -> Self {
-        Self {
-            id: event.id,
-            request_id: event.request_id,
-            bucky_bank_id: event.bucky_bank_id,
-            amount: event.amount,
-            left_balance: event.left_balance,
-            withdrawer: event.withdrawer,
-            created_at_ms: event.created_at_ms,
-            created_at: event.created_at,
+// 获取处理失败事件列表，可按是否已重放成功过滤
+pub async fn get_event_failures(
+    State(state): State<HealthState>,
+    Query(query): Query<EventFailureQuery>,
+) -> Result<Json<ApiResponse<Vec<EventFailure>>>, ApiError> {
+    let page = PageRequest::new::<EventFailure>(
+        query.page,
+        query.limit,
+        query.cursor.as_deref(),
+        query.include_total.unwrap_or(false),
+    )?;
+    match state.db.get_event_failures(query.resolved, &page).await {
+        Ok(failures) => Ok(Json(ApiResponse::page(failures))),
+        Err(e) => {
+            tracing::error!("Failed to get event failures: {}", e);
+            Err(ApiError::database("Failed to get event failures", e))
         }
     }
 }
 
-// 根据request_id获取单个提取请求
-pub async fn get_withdrawal_request_by_id(
+// server 模式下首次重放时才创建索引器，连接 RPC 失败返回 500
+async fn event_indexer(state: &HealthState) -> Result<Arc<BuckyBankIndexer>, ApiError> {
+    state.chain.indexer().await.map_err(|e| {
+        tracing::error!("Failed to create event indexer: {}", e);
+        ApiError::internal("Failed to create event indexer", e)
+    })
+}
+
+// 重放所有未处理的失败事件
+pub async fn replay_event_failures(
     State(state): State<HealthState>,
-    Path(request_id): Path<String>,
-) -> Result<Json<ApiResponse<WithdrawalRequestResponse>>, StatusCode> {
-    let db = &state.db;
-    match db.get_withdrawal_request_by_id(&request_id).await {
-        Ok(Some(request)) => {
-            let response = WithdrawalRequestResponse::from(request);
-            Ok(Json(ApiResponse::success(response)))
-        }
-        Ok(None) => Err(StatusCode::NOT_FOUND),
+    Query(query): Query<ReplayQuery>,
+) -> Result<Json<ApiResponse<ReplayResult>>, ApiError> {
+    let indexer = event_indexer(&state).await?;
+    match indexer.replay_failures(query.limit.unwrap_or(100), &state.shutdown).await {
+        Ok(result) => Ok(Json(ApiResponse::success(result))),
         Err(e) => {
-            tracing::error!("Failed to get withdrawal request: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            tracing::error!("Failed to replay event failures: {}", e);
+            Err(ApiError::internal("Failed to replay event failures", e))
         }
     }
 }
 
-// 根据bucky_bank_id获取提取请求列表
-pub async fn get_withdrawal_requests_by_bank_id(
+// 重放单个失败事件
+pub async fn replay_event_failure(
     State(state): State<HealthState>,
-    Path(bucky_bank_id): Path<String>,
-    Query(params): Query<PaginationParams>,
-) -> Result<Json<ApiResponse<Vec<WithdrawalRequestResponse>>>, StatusCode> {
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<ApiResponse<EventFailure>>, ApiError> {
     let db = &state.db;
-    match db
-        .get_withdrawal_requests_by_bank_id(&bucky_bank_id, params.limit, params.offset)
-        .await
-    {
-        Ok(requests) => {
-            let responses: Vec<WithdrawalRequestResponse> = requests
-                .into_iter()
-                .map(WithdrawalRequestResponse::from)
-                .collect();
-            Ok(Json(ApiResponse::success(responses)))
+    let failure = match db.get_event_failure_by_id(id).await {
+        Ok(Some(failure)) => failure,
+        Ok(None) => return Err(ApiError::not_found(format!("Event failure {} not found", id))),
+        Err(e) => {
+            tracing::error!("Failed to get event failure: {}", e);
+            return Err(ApiError::database("Failed to get event failure", e));
+        }
+    };
+
+    let resolved = match event_indexer(&state).await?.replay_failure(&failure).await {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            tracing::error!("Failed to replay event failure: {}", e);
+            return Err(ApiError::internal("Failed to replay event failure", e));
         }
+    };
+
+    // 重放后重新读取记录，失败时 error 字段已更新为本次重放的错误
+    let failure = match db.get_event_failure_by_id(id).await {
+        Ok(Some(failure)) => failure,
+        Ok(None) => return Err(ApiError::not_found(format!("Event failure {} not found", id))),
         Err(e) => {
-            tracing::error!("Failed to get withdrawal requests by bank id: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            tracing::error!("Failed to get event failure: {}", e);
+            return Err(ApiError::database("Failed to get event failure", e));
         }
+    };
+
+    if !resolved {
+        return Err(ApiError::conflict(format!(
+            "Event failure {} failed again: {}",
+            id, failure.error
+        )));
     }
+    Ok(Json(ApiResponse::success(failure)))
 }
 
-// 根据requester获取提取请求列表
-pub async fn get_withdrawal_requests_by_requester(
+// 余额对账相关API
+
+// 按存取款流水核对余额，只报告偏差不做修改
+pub async fn get_balance_reconciliation(
     State(state): State<HealthState>,
-    Path(requester): Path<String>,
-    Query(params): Query<PaginationParams>,
-) -> Result<Json<ApiResponse<Vec<WithdrawalRequestResponse>>>, StatusCode> {
+    Query(query): Query<ReconcileQuery>,
+) -> Result<Json<ApiResponse<ReconcileReport>>, ApiError> {
     let db = &state.db;
     match db
-        .get_withdrawal_requests_by_requester(&requester, params.limit, params.offset)
+        .reconcile_bucky_bank_balances(query.bucky_bank_id.as_deref(), false)
         .await
     {
-        Ok(requests) => {
-            let responses: Vec<WithdrawalRequestResponse> = requests
-                .into_iter()
-                .map(WithdrawalRequestResponse::from)
-                .collect();
-            Ok(Json(ApiResponse::success(responses)))
-        }
+        Ok(report) => Ok(Json(ApiResponse::success(report))),
         Err(e) => {
-            tracing::error!("Failed to get withdrawal requests by requester: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            tracing::error!("Failed to reconcile balances: {}", e);
+            Err(ApiError::database("Failed to reconcile balances", e))
         }
     }
 }
 
-// 根据状态获取提取请求列表
-pub async fn get_withdrawal_requests_by_status(
+// 按存取款流水核对余额并修正记录的余额
+pub async fn fix_balance_reconciliation(
     State(state): State<HealthState>,
-    Query(status_query): Query<WithdrawalStatusQuery>,
-    Query(params): Query<PaginationParams>,
-) -> Result<Json<ApiResponse<Vec<WithdrawalRequestResponse>>>, StatusCode> {
+    Query(query): Query<ReconcileQuery>,
+) -> Result<Json<ApiResponse<ReconcileReport>>, ApiError> {
     let db = &state.db;
-    let status_str = status_query.status.unwrap_or_else(|| "Pending".to_string());
-    
-    let status = match status_str.parse::<WithdrawalStatus>() {
-        Ok(s) => s,
-        Err(_) => {
-            return Ok(Json(ApiResponse::error(format!(
-                "Invalid status: {}. Valid values are: Pending, Approved, Rejected, Cancelled, Withdrawed",
-                status_str
-            ))));
-        }
-    };
-
     match db
-        .get_withdrawal_requests_by_status(&status, params.limit, params.offset)
+        .reconcile_bucky_bank_balances(query.bucky_bank_id.as_deref(), true)
         .await
     {
-        Ok(requests) => {
-            let responses: Vec<WithdrawalRequestResponse> = requests
-                .into_iter()
-                .map(WithdrawalRequestResponse::from)
-                .collect();
-            Ok(Json(ApiResponse::success(responses)))
+        Ok(report) => {
+            tracing::info!(
+                "Reconciled {} BuckyBanks: {} drifted, {} fixed",
+                report.checked,
+                report.drifted.len(),
+                report.fixed
+            );
+            Ok(Json(ApiResponse::success(report)))
         }
         Err(e) => {
-            tracing::error!("Failed to get withdrawal requests by status: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            tracing::error!("Failed to fix balances: {}", e);
+            Err(ApiError::database("Failed to fix balances", e))
         }
     }
 }
 
-// 获取提取请求统计信息
-pub async fn get_withdrawal_requests_stats(
+// 链上状态核对相关API
+
+// 读取链上BuckyBank对象并与索引数据核对
+pub async fn verify_bucky_bank(
     State(state): State<HealthState>,
-    Query(bank_id): Query<Option<String>>,
-) -> Result<Json<ApiResponse<serde_json::Value>>, StatusCode> {
-    let db = &state.db;
-    let bucky_bank_id = bank_id.as_deref();
-    
-    match db.get_withdrawal_requests_stats(bucky_bank_id).await {
-        Ok(stats) => Ok(Json(ApiResponse::success(stats))),
+    Path(bucky_bank_id): Path<String>,
+) -> Result<Json<ApiResponse<VerificationResult>>, ApiError> {
+    match state.verifier.verify(&bucky_bank_id).await {
+        Ok(result) => Ok(Json(ApiResponse::success(result))),
         Err(e) => {
-            tracing::error!("Failed to get withdrawal requests stats: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            tracing::error!("Failed to verify BuckyBank {}: {}", bucky_bank_id, e);
+            Err(ApiError::internal("Failed to verify BuckyBank", e))
         }
     }
 }
 
-#[derive(Debug, Deserialize)]
-pub struct UpdateStatusRequest {
-    pub status: String,
-    pub approved_by: Option<String>,
+// 获取链上状态核对指标
+pub async fn get_verifier_stats(
+    State(state): State<HealthState>,
+) -> Json<ApiResponse<VerifierStats>> {
+    Json(ApiResponse::success(state.verifier.stats()))
 }
 
-// 更新提取请求状态
-pub async fn update_withdrawal_request_status(
+// Webhook 订阅管理API
+
+// 获取所有 webhook 订阅，不返回签名密钥
+pub async fn get_webhook_subscriptions(
     State(state): State<HealthState>,
-    Path(request_id): Path<String>,
-    Json(payload): Json<UpdateStatusRequest>,
-) -> Result<Json<ApiResponse<String>>, StatusCode> {
-    let db = &state.db;
-    let status = match payload.status.parse::<WithdrawalStatus>() {
-        Ok(s) => s,
-        Err(_) => {
-            return Ok(Json(ApiResponse::error(format!(
-                "Invalid status: {}. Valid values are: Pending, Approved, Rejected, Cancelled, Withdrawed",
-                payload.status
-            ))));
+) -> Result<Json<ApiResponse<Vec<WebhookSubscription>>>, ApiError> {
+    match state.db.get_webhook_subscriptions().await {
+        Ok(subscriptions) => Ok(Json(ApiResponse::success(subscriptions))),
+        Err(e) => {
+            tracing::error!("Failed to get webhook subscriptions: {}", e);
+            Err(ApiError::database("Failed to get webhook subscriptions", e))
         }
-    };
+    }
+}
 
-    match db
-        .update_withdrawal_request_status(&request_id, &status, payload.approved_by.as_deref(), None)
-        .await
+// 创建 webhook 订阅，未指定 secret 时自动生成
+pub async fn create_webhook_subscription(
+    State(state): State<HealthState>,
+    Json(subscription): Json<NewWebhookSubscription>,
+) -> Result<Json<ApiResponse<CreatedWebhookSubscription>>, ApiError> {
+    if !subscription.url.starts_with("http://") && !subscription.url.starts_with("https://") {
+        return Err(ApiError::validation_with_details(
+            format!("Invalid webhook url: {}", subscription.url),
+            serde_json::json!({ "field": "url" }),
+        ));
+    }
+    if let Some(event_type) = subscription
+        .event_types
+        .iter()
+        .find(|event_type| !WEBHOOK_EVENT_TYPES.contains(&event_type.as_str()))
     {
-        Ok(true) => Ok(Json(ApiResponse::success("Status updated successfully".to_string()))),
-        Ok(false) => Err(StatusCode::NOT_FOUND),
+        return Err(ApiError::validation_with_details(
+            format!("Invalid event type: {}", event_type),
+            serde_json::json!({ "field": "event_types", "allowed": WEBHOOK_EVENT_TYPES }),
+        ));
+    }
+
+    let secret = match subscription.secret.as_deref() {
+        Some(secret) if !secret.is_empty() => secret.to_string(),
+        _ => format!(
+            "{}{}",
+            uuid::Uuid::new_v4().simple(),
+            uuid::Uuid::new_v4().simple()
+        ),
+    };
+
+    match state.db.create_webhook_subscription(&subscription, &secret).await {
+        Ok(subscription) => {
+            tracing::info!("Created webhook subscription {} for {}", subscription.id, subscription.url);
+            Ok(Json(ApiResponse::success(CreatedWebhookSubscription {
+                subscription,
+                secret,
+            })))
+        }
         Err(e) => {
-            tracing::error!("Failed to update withdrawal request status: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            tracing::error!("Failed to create webhook subscription: {}", e);
+            Err(ApiError::database("Failed to create webhook subscription", e))
         }
     }
 }
 
-// EventWithdrawed 事件相关API
-
-// 根据request_id获取单个提取完成事件
-pub async fn get_event_withdrawed_by_request_id(
+// 删除 webhook 订阅及其投递记录
+pub async fn delete_webhook_subscription(
     State(state): State<HealthState>,
-    Path(request_id): Path<String>,
-) -> Result<Json<ApiResponse<EventWithdrawedResponse>>, StatusCode> {
-    let db = &state.db;
-    match db.get_event_withdrawed_by_request_id(&request_id).await {
-        Ok(Some(event)) => {
-            let response = EventWithdrawedResponse::from(event);
-            Ok(Json(ApiResponse::success(response)))
-        }
-        Ok(None) => Err(StatusCode::NOT_FOUND),
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<ApiResponse<String>>, ApiError> {
+    match state.db.delete_webhook_subscription(id).await {
+        Ok(true) => Ok(Json(ApiResponse::success(format!("Webhook subscription {} deleted", id)))),
+        Ok(false) => Err(ApiError::not_found(format!("Webhook subscription {} not found", id))),
         Err(e) => {
-            tracing::error!("Failed to get event withdrawed: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            tracing::error!("Failed to delete webhook subscription: {}", e);
+            Err(ApiError::database("Failed to delete webhook subscription", e))
         }
     }
 }
 
-// 根据bucky_bank_id获取提取完成事件列表
-pub async fn get_event_withdrawed_by_bank_id(
+// 获取订阅的投递记录，可按状态过滤
+pub async fn get_webhook_deliveries(
     State(state): State<HealthState>,
-    Path(bucky_bank_id): Path<String>,
-    Query(params): Query<PaginationParams>,
-) -> Result<Json<ApiResponse<Vec<EventWithdrawedResponse>>>, StatusCode> {
-    let db = &state.db;
-    match db
-        .get_event_withdrawed_by_bank_id(&bucky_bank_id, params.limit, params.offset)
+    Path(id): Path<uuid::Uuid>,
+    Query(query): Query<WebhookDeliveryQuery>,
+) -> Result<Json<ApiResponse<Vec<WebhookDelivery>>>, ApiError> {
+    if let Some(status) = query.status.as_deref()
+        && !DELIVERY_STATUSES.contains(&status)
+    {
+        return Err(ApiError::validation_with_details(
+            format!("Invalid status: {}", status),
+            serde_json::json!({ "field": "status", "allowed": DELIVERY_STATUSES }),
+        ));
+    }
+
+    let page = PageRequest::new::<WebhookDelivery>(
+        query.page,
+        query.limit,
+        query.cursor.as_deref(),
+        query.include_total.unwrap_or(false),
+    )?;
+    match state
+        .db
+        .get_webhook_deliveries(id, query.status.as_deref(), &page)
         .await
     {
-        Ok(events) => {
-            let responses: Vec<EventWithdrawedResponse> = events
-                .into_iter()
-                .map(EventWithdrawedResponse::from)
-                .collect();
-            Ok(Json(ApiResponse::success(responses)))
-        }
+        Ok(deliveries) => Ok(Json(ApiResponse::page(deliveries))),
         Err(e) => {
-            tracing::error!("Failed to get event withdrawed by bank id: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            tracing::error!("Failed to get webhook deliveries: {}", e);
+            Err(ApiError::database("Failed to get webhook deliveries", e))
         }
     }
 }
 
-// 根据withdrawer获取提取完成事件列表
-pub async fn get_event_withdrawed_by_withdrawer(
+// 重新投递已失败的记录
+pub async fn retry_webhook_delivery(
     State(state): State<HealthState>,
-    Path(withdrawer): Path<String>,
-    Query(params): Query<PaginationParams>,
-) -> Result<Json<ApiResponse<Vec<EventWithdrawedResponse>>>, StatusCode> {
-    let db = &state.db;
-    match db
-        .get_event_withdrawed_by_withdrawer(&withdrawer, params.limit, params.offset)
-        .await
-    {
-        Ok(events) => {
-            let responses: Vec<EventWithdrawedResponse> = events
-                .into_iter()
-                .map(EventWithdrawedResponse::from)
-                .collect();
-            Ok(Json(ApiResponse::success(responses)))
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<ApiResponse<String>>, ApiError> {
+    match state.db.retry_webhook_delivery(id).await {
+        Ok(true) => Ok(Json(ApiResponse::success(format!("Webhook delivery {} queued for retry", id)))),
+        Ok(false) => Err(ApiError::not_found(format!("Failed webhook delivery {} not found", id))),
+        Err(e) => {
+            tracing::error!("Failed to retry webhook delivery: {}", e);
+            Err(ApiError::database("Failed to retry webhook delivery", e))
         }
+    }
+}
+
+// Sui 签名登录API
+
+#[derive(Debug, Deserialize)]
+pub struct ChallengeRequest {
+    pub address: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyChallengeRequest {
+    pub address: String,
+    pub nonce: uuid::Uuid,
+    pub signature: String, // 钱包 signPersonalMessage 返回的 base64 签名
+}
+
+fn parse_address(address: &str) -> Result<String, ApiError> {
+    auth::normalize_address(address).ok_or_else(|| {
+        ApiError::validation_with_details(
+            format!("Invalid address: {}", address),
+            serde_json::json!({ "field": "address" }),
+        )
+    })
+}
+
+// 生成登录挑战，客户端需要对返回的 message 做 personal message 签名
+pub async fn create_auth_challenge(
+    State(state): State<HealthState>,
+    Json(payload): Json<ChallengeRequest>,
+) -> Result<Json<ApiResponse<auth::AuthChallenge>>, ApiError> {
+    let address = parse_address(&payload.address)?;
+    match auth::create_challenge(state.db.get_pool(), &address).await {
+        Ok(challenge) => Ok(Json(ApiResponse::success(challenge))),
         Err(e) => {
-            tracing::error!("Failed to get event withdrawed by withdrawer: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            tracing::error!("Failed to create auth challenge: {}", e);
+            Err(ApiError::database("Failed to create auth challenge", e))
         }
     }
 }
 
-// 获取提取完成事件统计信息
-pub async fn get_event_withdrawed_stats(
+// 校验挑战签名并签发短期访问令牌，挑战无论成功与否都只能使用一次
+pub async fn verify_auth_challenge(
     State(state): State<HealthState>,
-    Query(bank_id): Query<Option<String>>,
-) -> Result<Json<ApiResponse<serde_json::Value>>, StatusCode> {
-    let db = &state.db;
-    let bucky_bank_id = bank_id.as_deref();
-    
-    match db.get_event_withdrawed_stats(bucky_bank_id).await {
-        Ok(stats) => Ok(Json(ApiResponse::success(stats))),
+    Json(payload): Json<VerifyChallengeRequest>,
+) -> Result<Json<ApiResponse<auth::AuthSession>>, ApiError> {
+    let address = parse_address(&payload.address)?;
+    let pool = state.db.get_pool();
+
+    let message = match auth::consume_challenge(pool, payload.nonce, &address).await {
+        Ok(Some(message)) => message,
+        Ok(None) => return Err(ApiError::unauthorized("Challenge not found, expired or already used")),
+        Err(e) => {
+            tracing::error!("Failed to load auth challenge: {}", e);
+            return Err(ApiError::database("Failed to load auth challenge", e));
+        }
+    };
+
+    if let Err(e) = crate::auth::verify_personal_message(&address, &message, &payload.signature) {
+        tracing::warn!("Rejected sign-in for {}: {}", address, e);
+        return Err(ApiError::unauthorized("Invalid signature"));
+    }
+
+    match auth::create_session(pool, &address).await {
+        Ok(session) => {
+            tracing::info!("Issued access token for {}", address);
+            Ok(Json(ApiResponse::success(session)))
+        }
         Err(e) => {
-            tracing::error!("Failed to get event withdrawed stats: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            tracing::error!("Failed to create auth session: {}", e);
+            Err(ApiError::database("Failed to create auth session", e))
         }
     }
-}
\ No newline at end of file
+}
@@ -1,8 +1,14 @@
-use axum::{extract::State, http::StatusCode, response::Json, routing::{get, put}, Router};
+use axum::{extract::State, http::{StatusCode, header}, middleware, response::{IntoResponse, Json, Response}, routing::{delete, get, post}, Router};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use crate::admin;
+use crate::chain::ChainServices;
 use crate::database::Database;
 use crate::handlers;
+use crate::metrics::METRICS;
+use crate::shutdown::Shutdown;
+use crate::state::{IndexerState, IndexerStatus};
+use crate::verifier::StateVerifier;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HealthResponse {
@@ -10,6 +16,9 @@ pub struct HealthResponse {
     pub database: String,
     pub timestamp: String,
     pub uptime_seconds: u64,
+    /// 轮询进度，只在同进程内运行轮询(`run` 模式)时返回
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indexer: Option<IndexerStatus>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -21,27 +30,53 @@ pub struct ErrorResponse {
 #[derive(Clone)]
 pub struct HealthState {
     pub db: Arc<Database>,
+    /// 重放接口使用的索引器按需创建，见 [`ChainServices`]
+    pub chain: Arc<ChainServices>,
+    pub verifier: Arc<StateVerifier<ChainServices>>,
+    pub polling_state: Option<Arc<IndexerState>>,
+    /// 批量重放在收到关闭信号后停止，HTTP 服务可以尽快退出
+    pub shutdown: Shutdown,
     pub start_time: std::time::Instant,
 }
 
 pub fn health_routes(state: HealthState) -> Router {
+    // 统一的 /api/v1 接口，与后端共用
+    let api_v1 = bucky_bank_api::router(state.db.get_pool().clone());
+
     Router::new()
         .route("/health", get(health_check))
         .route("/ready", get(readiness_check))
         .route("/live", get(liveness_check))
-        // 提取请求相关API
-        .route("/api/withdrawal-requests/:request_id", get(handlers::get_withdrawal_request_by_id))
-        .route("/api/withdrawal-requests/bank/:bucky_bank_id", get(handlers::get_withdrawal_requests_by_bank_id))
-        .route("/api/withdrawal-requests/requester/:requester", get(handlers::get_withdrawal_requests_by_requester))
-        .route("/api/withdrawal-requests/status", get(handlers::get_withdrawal_requests_by_status))
-        .route("/api/withdrawal-requests/stats", get(handlers::get_withdrawal_requests_stats))
-        .route("/api/withdrawal-requests/:request_id/status", put(handlers::update_withdrawal_request_status))
-        // EventWithdrawed 事件相关API
-        .route("/api/event-withdrawed/:request_id", get(handlers::get_event_withdrawed_by_request_id))
-        .route("/api/event-withdrawed/bank/:bucky_bank_id", get(handlers::get_event_withdrawed_by_bank_id))
-        .route("/api/event-withdrawed/withdrawer/:withdrawer", get(handlers::get_event_withdrawed_by_withdrawer))
-        .route("/api/event-withdrawed/stats", get(handlers::get_event_withdrawed_stats))
+        .route("/metrics", get(metrics))
+        // Sui 签名登录API，签发的令牌用于查看提取原因等私密字段
+        .route("/api/auth/challenge", post(handlers::create_auth_challenge))
+        .route("/api/auth/verify", post(handlers::verify_auth_challenge))
+        // 链上状态核对API
+        .route("/api/verify", get(handlers::get_verifier_stats))
+        .route("/api/verify/:bucky_bank_id", get(handlers::verify_bucky_bank))
+        .merge(admin_routes(state.clone()))
         .with_state(state)
+        .merge(api_v1)
+}
+
+// 管理接口，全部需要 API Key 认证
+fn admin_routes(state: HealthState) -> Router<HealthState> {
+    Router::new()
+        // 余额对账管理API
+        .route("/api/admin/reconcile", get(handlers::get_balance_reconciliation).post(handlers::fix_balance_reconciliation))
+        // 提取请求状态人工修正API
+        .route("/api/admin/withdrawal-requests/:request_id/corrections", post(handlers::correct_withdrawal_request_status))
+        .route("/api/admin/withdrawal-corrections", get(handlers::get_withdrawal_status_corrections))
+        // Webhook 订阅管理API
+        .route("/api/admin/webhooks", get(handlers::get_webhook_subscriptions).post(handlers::create_webhook_subscription))
+        .route("/api/admin/webhooks/:id", delete(handlers::delete_webhook_subscription))
+        .route("/api/admin/webhooks/:id/deliveries", get(handlers::get_webhook_deliveries))
+        .route("/api/admin/webhook-deliveries/:id/retry", post(handlers::retry_webhook_delivery))
+        // 处理失败事件（死信）查询与重放API
+        .route("/api/event-failures", get(handlers::get_event_failures))
+        .route("/api/event-failures/replay", post(handlers::replay_event_failures))
+        .route("/api/event-failures/:id/replay", post(handlers::replay_event_failure))
+        .route_layer(middleware::from_fn_with_state(state, admin::require_admin_key))
 }
 
 pub async fn health_check(
@@ -55,6 +90,7 @@ pub async fn health_check(
                 database: "connected".to_string(),
                 timestamp: chrono::Utc::now().to_rfc3339(),
                 uptime_seconds: state.start_time.elapsed().as_secs(),
+                indexer: state.polling_state.as_ref().map(|polling| polling.snapshot()),
             };
             Ok(Json(response))
         }
@@ -74,11 +110,29 @@ pub async fn readiness_check(
     // 就绪性检查 - 服务是否准备好接收请求
     match state.db.health_check().await {
         Ok(_) => {
+            // 同进程内运行轮询时，轮询停止、长时间没有成功轮询或索引延迟过大都视为未就绪
+            let indexer = match &state.polling_state {
+                Some(polling) => {
+                    let status = polling.snapshot();
+                    let problems = polling.readiness_problems(&status);
+                    if !problems.is_empty() {
+                        let error_response = ErrorResponse {
+                            error: "Indexer not ready".to_string(),
+                            message: problems.join("; "),
+                        };
+                        return Err((StatusCode::SERVICE_UNAVAILABLE, Json(error_response)));
+                    }
+                    Some(status)
+                }
+                None => None,
+            };
+
             let response = serde_json::json!({
                 "ready": true,
                 "timestamp": chrono::Utc::now().to_rfc3339(),
                 "checks": {
-                    "database": "ready"
+                    "database": "ready",
+                    "indexer": indexer
                 }
             });
             Ok(Json(response))
@@ -93,6 +147,21 @@ pub async fn readiness_check(
     }
 }
 
+// Prometheus 指标：事件处理计数与耗时、RPC 耗时与错误、索引延迟和数据库连接池
+pub async fn metrics(State(state): State<HealthState>) -> Response {
+    match METRICS.render(state.db.get_pool()) {
+        Ok(body) => (
+            [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+            body,
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to render metrics: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
 pub async fn liveness_check() -> Json<serde_json::Value> {
     // 存活性检查 - 服务是否正在运行
     let response = serde_json::json!({
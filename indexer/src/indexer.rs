@@ -1,15 +1,25 @@
+use crate::config::{EventEncoding, FailurePolicy, IndexingConfig};
+use crate::events::{self, MoveEvent};
+use crate::metrics::METRICS;
+use crate::shutdown::Shutdown;
+use crate::sink::{EventSink, SinkEvent};
+use crate::state::IndexerState;
 use crate::database::{
     Database,
-    models::{NewBuckyBankCreatedEvent, NewDepositMadeEvent, NewWithdrawalRequestEvent, NewEventWithdrawedEvent, NewCursor, WithdrawalStatus},
+    models::{NewBuckyBankCreatedEvent, NewDepositMadeEvent, NewWithdrawalRequestEvent, NewEventWithdrawedEvent, NewBankRewardEvent, NewRewardClaimEvent, NewCursor, NewEventFailure, EventFailure, WithdrawalStatus},
 };
 use anyhow::Result;
-use std::sync::Arc;
+use bucky_bank_api::EventNotification;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use sui_sdk::SuiClient;
 use sui_sdk::rpc_types::{EventFilter, SuiEvent};
 use sui_sdk::types::Identifier;
 use sui_sdk::types::base_types::ObjectID;
 use sui_sdk::types::event::EventID;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 #[derive(Debug)]
 pub struct EventProcessingResult {
@@ -17,6 +27,162 @@ pub struct EventProcessingResult {
     pub has_next_page: bool,
 }
 
+/// 死信事件重放结果
+#[derive(Debug, Default, Serialize)]
+pub struct ReplayResult {
+    pub replayed: usize,
+    pub resolved: usize,
+    pub failed: usize,
+}
+
+/// 事件处理所需的链上事件数据，可来自 RPC 查询的 SuiEvent，也可来自 event_failures 死信记录
+#[derive(Debug, Clone)]
+pub struct IndexedEvent {
+    pub id: EventID,
+    pub parsed_json: serde_json::Value,
+    /// 事件的 BCS 字节，死信记录中没有保存
+    pub bcs: Option<Vec<u8>>,
+    pub timestamp_ms: Option<u64>,
+}
+
+impl From<&SuiEvent> for IndexedEvent {
+    fn from(event: &SuiEvent) -> Self {
+        Self {
+            id: event.id,
+            parsed_json: event.parsed_json.clone(),
+            bcs: Some(event.bcs.bytes().to_vec()),
+            timestamp_ms: event.timestamp_ms,
+        }
+    }
+}
+
+impl TryFrom<&EventFailure> for IndexedEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(failure: &EventFailure) -> Result<Self> {
+        Ok(Self {
+            id: EventID {
+                tx_digest: failure.tx_digest.parse()?,
+                event_seq: failure.event_seq as u64,
+            },
+            parsed_json: failure.parsed_json.clone(),
+            bcs: None,
+            timestamp_ms: failure.timestamp_ms.map(|ts| ts as u64),
+        })
+    }
+}
+
+/// 回填时预取的事件页数，写库较慢时 RPC 查询最多领先这么多页
+const BACKFILL_PREFETCH_PAGES: usize = 8;
+
+/// 历史事件回填范围，未指定的边界表示不限制
+#[derive(Debug, Default, Clone)]
+pub struct BackfillRange {
+    /// 从该事件之后开始回填（不包含该事件）
+    pub from_cursor: Option<EventID>,
+    /// 回填到该事件为止（包含该事件）
+    pub to_cursor: Option<EventID>,
+    /// 只回填时间戳不早于该值的事件(毫秒)
+    pub from_timestamp_ms: Option<u64>,
+    /// 只回填时间戳不晚于该值的事件(毫秒)
+    pub to_timestamp_ms: Option<u64>,
+}
+
+/// 历史事件回填结果
+#[derive(Debug, Default, Serialize)]
+pub struct BackfillResult {
+    pub pages: usize,
+    pub fetched: usize,
+    pub saved: u64,
+    pub duplicates: u64,
+    pub failed: usize,
+    /// 收到关闭信号后在页之间提前结束
+    pub interrupted: bool,
+}
+
+/// 解析 `tx_digest:event_seq` 格式的事件游标
+pub fn parse_event_cursor(value: &str) -> Result<EventID> {
+    let (tx_digest, event_seq) = value
+        .split_once(':')
+        .ok_or_else(|| anyhow::anyhow!("Invalid event cursor {}, expected tx_digest:event_seq", value))?;
+
+    Ok(EventID {
+        tx_digest: tx_digest.parse()?,
+        event_seq: event_seq.parse()?,
+    })
+}
+
+/// 回填时解析完成、等待写入的事件
+struct BackfillItem {
+    event: IndexedEvent,
+    event_type: EventType,
+    sink_event: SinkEvent,
+}
+
+/// 提取请求审批结果，来自 EventWithdrawalApproved / EventWithdrawalRejected
+#[derive(Debug, Clone, Serialize)]
+pub struct WithdrawalAudit {
+    pub request_id: String,
+    pub bucky_bank_id: String,
+    pub amount: i64,
+    pub requester: String,
+    pub reason: String,
+    pub status: WithdrawalStatus,
+    pub audited_by: String,
+    pub audit_at_ms: i64,
+}
+
+/// 按事件类型解析后的事件数据，交给 EventSink 写入
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum BuckyBankEvent {
+    BuckyBankCreated(NewBuckyBankCreatedEvent),
+    DepositMade(NewDepositMadeEvent),
+    WithdrawalRequested(NewWithdrawalRequestEvent),
+    WithdrawalAudited(WithdrawalAudit),
+    EventWithdrawed(NewEventWithdrawedEvent),
+    BankReward(NewBankRewardEvent),
+    ClaimReward(NewRewardClaimEvent),
+}
+
+impl BuckyBankEvent {
+    /// 事件所属的 BuckyBank 和事件中的操作者地址
+    pub fn subject(&self) -> (&str, &str) {
+        match self {
+            BuckyBankEvent::BuckyBankCreated(event) => (&event.bucky_bank_id, &event.parent_address),
+            BuckyBankEvent::DepositMade(event) => (&event.bucky_bank_id, &event.depositor),
+            BuckyBankEvent::WithdrawalRequested(event) => (&event.bucky_bank_id, &event.requester),
+            BuckyBankEvent::WithdrawalAudited(audit) => (&audit.bucky_bank_id, &audit.audited_by),
+            BuckyBankEvent::EventWithdrawed(event) => (&event.bucky_bank_id, &event.withdrawer),
+            BuckyBankEvent::BankReward(event) => (&event.bucky_bank_id, &event.reward_receiver),
+            BuckyBankEvent::ClaimReward(event) => (&event.bucky_bank_id, &event.reward_receiver),
+        }
+    }
+}
+
+/// 存款使存钱罐达成目标时推送的 webhook 事件类型，不对应链上事件
+pub const TARGET_REACHED_EVENT: &str = "TargetReached";
+
+/// 事件多次处理失败且策略为 Halt 时返回，轮询循环收到后停止索引器
+#[derive(Debug)]
+pub struct EventProcessingHalted {
+    pub event_id: EventID,
+    pub event_type: &'static str,
+    pub error: anyhow::Error,
+}
+
+impl std::fmt::Display for EventProcessingHalted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Indexing halted at {} event {:?}: {}",
+            self.event_type, self.event_id, self.error
+        )
+    }
+}
+
+impl std::error::Error for EventProcessingHalted {}
+
 #[derive(Debug, Clone)]
 pub enum EventType {
     BuckyBankCreated,
@@ -25,10 +191,12 @@ pub enum EventType {
     WithdrawalApproved,
     WithdrawalRejected,
     EventWithdrawed,
+    BankReward,
+    ClaimReward,
 }
 
 impl EventType {
-    pub fn name(&self) -> &'static str {
+    pub const fn name(&self) -> &'static str {
         match self {
             EventType::BuckyBankCreated => "BuckyBankCreated",
             EventType::DepositMade => "DepositMade",
@@ -36,11 +204,16 @@ impl EventType {
             EventType::WithdrawalApproved => "EventWithdrawalApproved",
             EventType::WithdrawalRejected => "EventWithdrawalRejected",
             EventType::EventWithdrawed => "EventWithdrawed",
+            EventType::BankReward => "EventBankReward",
+            EventType::ClaimReward => "EventClaimReward",
         }
     }
 
-    pub fn full_type(&self, package_id: &str, module_name: &str) -> String {
-        format!("{}::{}::{}", package_id, module_name, self.name())
+    /// 根据链上事件结构体名称解析事件类型，不属于索引范围的事件返回 None
+    pub fn from_name(name: &str) -> Option<EventType> {
+        EventType::all_event_types()
+            .into_iter()
+            .find(|event_type| event_type.name() == name)
     }
 
     pub fn all_event_types() -> Vec<EventType> {
@@ -51,6 +224,8 @@ impl EventType {
             EventType::WithdrawalApproved,
             EventType::WithdrawalRejected,
             EventType::EventWithdrawed,
+            EventType::BankReward,
+            EventType::ClaimReward,
         ]
     }
 }
@@ -60,6 +235,17 @@ pub struct BuckyBankIndexer {
     package_id: String,
     module_name: String,
     db: Arc<Database>,
+    sink: Arc<dyn EventSink>,
+    query_limit: usize,
+    poll_interval: Duration,
+    max_retries: u32,
+    max_event_attempts: u32,
+    failure_policy: FailurePolicy,
+    event_encoding: EventEncoding,
+    batch_size: usize,
+    state: Arc<IndexerState>,
+    /// 存钱罐的家长和孩子地址，创建后不会变化，缓存后生成通知时每个存钱罐只查询一次数据库
+    bank_addresses: Mutex<HashMap<String, (String, String)>>,
 }
 
 impl BuckyBankIndexer {
@@ -67,186 +253,613 @@ impl BuckyBankIndexer {
         client: Arc<SuiClient>,
         package_id: String,
         module_name: String,
+        query_limit: usize,
         db: Arc<Database>,
+        sink: Arc<dyn EventSink>,
+        indexing: &IndexingConfig,
     ) -> Self {
         Self {
             client,
             package_id,
             module_name,
             db,
+            sink,
+            query_limit,
+            poll_interval: Duration::from_secs(indexing.poll_interval_seconds),
+            max_retries: indexing.max_retries,
+            max_event_attempts: indexing.max_event_attempts.max(1),
+            failure_policy: indexing.failure_policy,
+            event_encoding: indexing.event_encoding,
+            batch_size: indexing.batch_size.max(1),
+            state: Arc::new(IndexerState::new(indexing)),
+            bank_addresses: Mutex::new(HashMap::new()),
         }
     }
 
-    pub async fn query_and_process_events(&self) -> Result<EventProcessingResult> {
-        info!("Querying all BuckyBank events...");
-
-        let mut total_processed = 0;
-        let mut has_next_page = false;
-
-        for event_type in EventType::all_event_types() {
-            info!("Processing {} events...", event_type.name());
-
-            match self.query_and_process_events_for_type(&event_type).await {
-                Ok((count, next_page)) => {
-                    total_processed += count;
-                    has_next_page = has_next_page || next_page;
-                    info!("Processed {} {} events", count, event_type.name());
-                }
-                Err(e) => {
-                    error!("Failed to process {} events: {}", event_type.name(), e);
-                }
-            }
-        }
+    /// 轮询状态，`run` 模式下由 HTTP 服务读取
+    pub fn state(&self) -> Arc<IndexerState> {
+        self.state.clone()
+    }
 
-        Ok(EventProcessingResult {
-            total_processed,
-            has_next_page,
-        })
+    /// 模块级游标ID，整个模块的事件流共用一个游标
+    fn cursor_id(&self) -> String {
+        format!("{}::{}", self.package_id, self.module_name)
     }
 
-    pub async fn query_and_process_events_for_type(&self, event_type: &EventType) -> Result<(usize, bool)> {
-        let full_event_type = event_type.full_type(&self.package_id, &self.module_name);
+    /// 单次遍历模块事件流，按链上顺序将每个事件分发给对应的处理器
+    pub async fn query_and_process_events(&self, shutdown: &Shutdown) -> Result<EventProcessingResult> {
+        let cursor_id = self.cursor_id();
 
-        info!(">>> Querying {} events with cursor...", event_type.name());
+        info!(">>> Querying {} events with cursor...", cursor_id);
 
         // 获取最新的游标
-        let cursor_data = self.get_latest_cursor(&full_event_type).await?;
+        let cursor_data = self.get_latest_cursor(&cursor_id).await?;
 
         let package_id: ObjectID = self.package_id.parse()?;
         let module = Identifier::new(&*self.module_name)?;
 
         // 使用游标查询事件，如果没有游标则从头开始
-        let result = if let Some((tx_digest, event_seq)) = cursor_data {
+        let cursor = if let Some((tx_digest, event_seq)) = cursor_data {
             info!(
                 "Using cursor for {}: tx_digest={}, event_seq={}",
-                event_type.name(), tx_digest, event_seq
+                cursor_id, tx_digest, event_seq
             );
-            let event_id = EventID {
+            Some(EventID {
                 tx_digest: tx_digest.parse()?,
                 event_seq: event_seq.parse()?,
-            };
-            self.client
-                .event_api()
-                .query_events(
-                    EventFilter::MoveModule {
-                        package: package_id,
-                        module: module.clone(),
-                    },
-                    Some(event_id),
-                    None,
-                    false, // 按升序排列
-                )
-                .await?
+            })
         } else {
-            info!("No cursor found for {}, starting from beginning", event_type.name());
-            self.client
-                .event_api()
-                .query_events(
-                    EventFilter::MoveModule {
-                        package: package_id,
-                        module,
-                    },
-                    None,
-                    None,
-                    false, // 按升序排列
-                )
-                .await?
+            info!("No cursor found for {}, starting from beginning", cursor_id);
+            None
         };
 
-        info!("Found {} events for {}", result.data.len(), event_type.name());
-        info!("Has next page for {}: {}", event_type.name(), result.has_next_page);
+        let started = Instant::now();
+        let result = self
+            .client
+            .event_api()
+            .query_events(
+                EventFilter::MoveModule {
+                    package: package_id,
+                    module,
+                },
+                cursor,
+                Some(self.query_limit),
+                false, // 按升序排列
+            )
+            .await;
+        METRICS.observe_rpc("query_events", started, &result);
+        let result = result?;
+
+        info!("Found {} events for {}", result.data.len(), cursor_id);
+        info!("Has next page for {}: {}", cursor_id, result.has_next_page);
 
-        let mut processed_count = 0;
-        let mut latest_tx_digest = None;
-        let mut latest_event_seq = None;
+        let mut total_processed = 0;
+        // 已处理的事件会在写入事务内推进游标，这里只记录尚未写入游标的跳过事件
+        let mut skipped_event_id = None;
 
         for event in result.data {
             debug!("Processing event: {:?}", event.id);
 
-            // 检查事件类型并处理
-            if self.matches_event_type(&event, event_type) {
-                match self.process_event(&event, event_type).await {
-                    Ok(_) => {
-                        processed_count += 1;
-                        latest_tx_digest = Some(event.id.tx_digest.to_string());
-                        latest_event_seq = Some(event.id.event_seq.to_string());
-                        info!(
-                            "Successfully processed {} event: {:?}",
-                            event_type.name(), event.id
+            // 不在索引范围内的事件直接跳过，但游标仍然前移
+            let Some(event_type) = EventType::from_name(event.type_.name.as_str()) else {
+                debug!("Skipping unindexed event type {}: {:?}", event.type_.name, event.id);
+                skipped_event_id = Some(event.id);
+                continue;
+            };
+
+            let cursor = NewCursor {
+                id: cursor_id.clone(),
+                event_seq: event.id.event_seq.to_string(),
+                tx_digest: event.id.tx_digest.to_string(),
+            };
+
+            let indexed_event = IndexedEvent::from(&event);
+
+            match self
+                .process_event_with_retries(&indexed_event, &event_type, &cursor, shutdown)
+                .await
+            {
+                Ok(_) => {
+                    total_processed += 1;
+                    skipped_event_id = None;
+                    info!(
+                        "Successfully processed {} event: {:?}",
+                        event_type.name(), event.id
+                    );
+                }
+                Err(e) if shutdown.is_requested() => {
+                    // 重试被关闭信号打断，游标停在该事件之前，下次启动时重新处理
+                    warn!(
+                        "Shutdown requested while retrying {} event {:?}, leaving it for the next run: {}",
+                        event_type.name(), event.id, e
+                    );
+                    self.advance_cursor_past_skipped(&cursor_id, skipped_event_id, total_processed)
+                        .await?;
+                    return Ok(EventProcessingResult {
+                        total_processed,
+                        has_next_page: true,
+                    });
+                }
+                Err(e) => match self.failure_policy {
+                    FailurePolicy::DeadLetter => {
+                        let failure = NewEventFailure {
+                            tx_digest: event.id.tx_digest.to_string(),
+                            event_seq: event.id.event_seq as i64,
+                            event_type: event_type.name().to_string(),
+                            parsed_json: event.parsed_json.clone(),
+                            error: e.to_string(),
+                            attempts: self.max_event_attempts as i32,
+                            timestamp_ms: event.timestamp_ms.map(|ts| ts as i64),
+                        };
+                        self.db.save_event_failure(&failure, Some(&cursor)).await?;
+                        skipped_event_id = None;
+                        warn!(
+                            "Moved {} event {:?} to event_failures after {} attempts: {}",
+                            event_type.name(), event.id, self.max_event_attempts, e
                         );
                     }
-                    Err(e) => {
+                    FailurePolicy::Halt => {
+                        // 游标停在失败事件之前，重启后从该事件继续
+                        self.advance_cursor_past_skipped(&cursor_id, skipped_event_id, total_processed)
+                            .await?;
+                        return Err(EventProcessingHalted {
+                            event_id: event.id,
+                            event_type: event_type.name(),
+                            error: e,
+                        }
+                        .into());
+                    }
+                },
+            }
+        }
+
+        self.advance_cursor_past_skipped(&cursor_id, skipped_event_id, total_processed)
+            .await?;
+
+        info!("Processed {} events for {}", total_processed, cursor_id);
+        Ok(EventProcessingResult {
+            total_processed,
+            has_next_page: result.has_next_page,
+        })
+    }
+
+    /// 持续轮询直到收到关闭信号；正在处理的一页会完整处理完并写入游标后才退出，
+    /// 只有正在等待重试的事件会被中断，留到下次启动时处理
+    pub async fn run_continuous_polling(&self, shutdown: &Shutdown) -> Result<()> {
+        self.state.mark_started();
+        let result = self.poll_until_shutdown(shutdown).await;
+        self.state
+            .mark_stopped(result.as_ref().err().map(|e| e.to_string()).as_deref());
+        result
+    }
+
+    async fn poll_until_shutdown(&self, shutdown: &Shutdown) -> Result<()> {
+        // 连续失败次数，用于计算指数退避时间
+        let mut consecutive_failures = 0;
+        // 追赶历史事件时不逐页刷新统计，追上最新事件后再统一刷新
+        let mut stats_stale = false;
+
+        while !shutdown.is_requested() {
+            info!("next loop...");
+
+            // 不与关闭信号竞争，避免一页事件只处理了一部分
+            match self.query_and_process_events(shutdown).await {
+                Ok(result) => {
+                    consecutive_failures = 0;
+                    self.state.record_poll(result.has_next_page);
+                    stats_stale |= result.total_processed > 0;
+
+                    if result.has_next_page {
+                        // 如果有下一页，立即继续处理
+                        info!("Has more pages, continuing immediately...");
+                        continue;
+                    }
+
+                    METRICS.mark_caught_up();
+                    if stats_stale {
+                        self.refresh_platform_stats().await;
+                        stats_stale = false;
+                    }
+
+                    // 已追上链上最新事件，按照配置的间隔轮询
+                    info!(
+                        "Processed {} events, no more pages, waiting {:?} for next poll cycle...",
+                        result.total_processed, self.poll_interval
+                    );
+                    shutdown.sleep(self.poll_interval).await;
+                    info!("wake up...");
+                }
+                Err(e) if e.is::<EventProcessingHalted>() => {
+                    error!("Stopping indexer: {}", e);
+                    return Err(e);
+                }
+                Err(e) => {
+                    consecutive_failures += 1;
+                    self.state.record_error(&e.to_string());
+                    if consecutive_failures > self.max_retries {
                         error!(
-                            "Failed to process {} event {:?}: {}",
-                            event_type.name(), event.id, e
+                            "Polling failed {} times in a row, giving up: {}",
+                            consecutive_failures, e
                         );
+                        return Err(e);
                     }
+
+                    // 指数退避：1s, 2s, 4s ...
+                    let backoff = Duration::from_secs(1 << (consecutive_failures - 1).min(6));
+                    error!(
+                        "Error during polling (attempt {}/{}), retrying in {:?}: {}",
+                        consecutive_failures, self.max_retries, backoff, e
+                    );
+                    shutdown.sleep(backoff).await;
                 }
             }
         }
 
-        // 更新游标
-        if let (Some(tx_digest), Some(event_seq)) = (latest_tx_digest, latest_event_seq) {
-            if processed_count > 0 {
-                self.update_cursor(&full_event_type, &tx_digest, &event_seq)
-                    .await?;
+        // 退出前刷新追赶期间未刷新的统计
+        if stats_stale {
+            self.refresh_platform_stats().await;
+        }
+        info!("Received shutdown signal, polling stopped after finishing the in-flight page");
+        Ok(())
+    }
+
+    // 本页已处理事件之后的跳过事件没有随事件写入推进游标，这里单独更新
+    async fn advance_cursor_past_skipped(
+        &self,
+        cursor_id: &str,
+        skipped_event_id: Option<EventID>,
+        total_processed: usize,
+    ) -> Result<()> {
+        if let Some(event_id) = skipped_event_id {
+            let tx_digest = event_id.tx_digest.to_string();
+            let event_seq = event_id.event_seq.to_string();
+            self.update_cursor(cursor_id, &tx_digest, &event_seq).await?;
+            info!(
+                "Updated cursor for {} to tx_digest={}, event_seq={} after processing {} events",
+                cursor_id, tx_digest, event_seq, total_processed
+            );
+        }
+        Ok(())
+    }
+
+    /// 处理单个事件，失败时最多尝试 max_event_attempts 次，每次失败后等待时间递增；
+    /// 等待期间收到关闭信号时不再重试，返回最近一次的错误
+    async fn process_event_with_retries(
+        &self,
+        event: &IndexedEvent,
+        event_type: &EventType,
+        cursor: &NewCursor,
+        shutdown: &Shutdown,
+    ) -> Result<()> {
+        let mut attempt = 1;
+        loop {
+            match self.process_event(event, event_type, Some(cursor)).await {
+                Ok(_) => return Ok(()),
+                Err(e) if attempt >= self.max_event_attempts => return Err(e),
+                Err(e) => {
+                    warn!(
+                        "Attempt {}/{} to process {} event {:?} failed: {}",
+                        attempt, self.max_event_attempts, event_type.name(), event.id, e
+                    );
+                    if !shutdown
+                        .sleep(Duration::from_millis(500 * attempt as u64))
+                        .await
+                    {
+                        return Err(e);
+                    }
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// 按写入顺序重放未处理的死信事件，重放不会移动游标；收到关闭信号后不再重放剩余事件
+    pub async fn replay_failures(&self, limit: i64, shutdown: &Shutdown) -> Result<ReplayResult> {
+        let failures = self.db.get_unresolved_event_failures(limit).await?;
+
+        let mut result = ReplayResult::default();
+        for failure in &failures {
+            if shutdown.is_requested() {
+                info!("Received shutdown signal, stopping replay");
+                break;
+            }
+            result.replayed += 1;
+            if self.replay_failure(failure).await? {
+                result.resolved += 1;
+            } else {
+                result.failed += 1;
+            }
+        }
+
+        info!(
+            "Replayed {} failed events: {} resolved, {} still failing",
+            result.replayed, result.resolved, result.failed
+        );
+        if result.resolved > 0 {
+            self.refresh_platform_stats().await;
+        }
+        Ok(result)
+    }
+
+    /// 通过正常的 process_event 路径重放单个死信事件，返回是否重放成功
+    pub async fn replay_failure(&self, failure: &EventFailure) -> Result<bool> {
+        let event_type = EventType::from_name(&failure.event_type).ok_or_else(|| {
+            anyhow::anyhow!("Unknown event type in event_failures: {}", failure.event_type)
+        })?;
+        let event = IndexedEvent::try_from(failure)?;
+
+        match self.process_event(&event, &event_type, None).await {
+            Ok(_) => {
+                self.db.resolve_event_failure(failure.id).await?;
                 info!(
-                    "Updated cursor for {} to tx_digest={}, event_seq={} after processing {} events",
-                    event_type.name(), tx_digest, event_seq, processed_count
+                    "Replayed {} event {:?} from event_failures",
+                    event_type.name(), event.id
                 );
+                Ok(true)
+            }
+            Err(e) => {
+                self.db
+                    .record_event_failure_attempt(failure.id, &e.to_string())
+                    .await?;
+                warn!(
+                    "Replay of {} event {:?} failed again: {}",
+                    event_type.name(), event.id, e
+                );
+                Ok(false)
             }
         }
-
-        info!("Processed {} {} events", processed_count, event_type.name());
-        Ok((processed_count, result.has_next_page))
     }
 
-    pub async fn run_continuous_polling(&self) -> Result<()> {
-        loop {
-            info!("next loop...");
+    /// 按范围回填历史事件，用于向空库重建索引
+    ///
+    /// Sui 的事件分页依赖上一页返回的游标，页之间只能顺序查询，
+    /// 因此这里让 RPC 查询与写库并发进行：查询任务持续预取后续页，
+    /// 写库任务把连续的同类型事件按 batch_size 分批写入 save_*_events_batch。
+    /// 回填不依赖模块游标，只有在尚无游标且从头回填时才会在结束后写入游标。
+    /// 收到关闭信号后两个任务都在页之间停止，已写入的页不会回滚。
+    pub async fn backfill(&self, range: &BackfillRange, shutdown: &Shutdown) -> Result<BackfillResult> {
+        let cursor_id = self.cursor_id();
+        let package_id: ObjectID = self.package_id.parse()?;
+        let module = Identifier::new(&*self.module_name)?;
 
-            // 创建一个 pinned 的 ctrl_c future
-            let ctrl_c = tokio::signal::ctrl_c();
-            tokio::pin!(ctrl_c);
-
-            // 使用 select! 同时监听轮询和关闭信号
-            tokio::select! {
-                // 轮询事件
-                poll_result = self.query_and_process_events() => {
-                    match poll_result {
-                        Ok(result) => {
-                            if result.has_next_page {
-                                // 如果有下一页，立即继续处理
-                                info!("Has more pages, continuing immediately...");
-                                continue;
-                            } else if result.total_processed > 0 {
-                                // 如果处理了事件但没有了下一页，短暂等待后继续
-                                info!("Processed events but no more pages, waiting 1 second...");
-                                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
-                            } else {
-                                // 如果没有处理任何事件，按照配置的间隔轮询
-                                info!("No new events, waiting for next poll cycle...");
-                                tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
-                                info!("wake up...");
-                            }
-                        }
+        info!(">>> Backfilling {} events in range {:?}", cursor_id, range);
+
+        let (sender, receiver) =
+            tokio::sync::mpsc::channel::<Vec<SuiEvent>>(BACKFILL_PREFETCH_PAGES);
+
+        // 查询任务：顺序翻页，按范围过滤后交给写库任务
+        let fetch = async move {
+            let mut cursor = range.from_cursor;
+            let mut pages = 0;
+            let mut interrupted = false;
+
+            loop {
+                if shutdown.is_requested() {
+                    interrupted = true;
+                    break;
+                }
+
+                let started = Instant::now();
+                let page = self
+                    .client
+                    .event_api()
+                    .query_events(
+                        EventFilter::MoveModule {
+                            package: package_id,
+                            module: module.clone(),
+                        },
+                        cursor,
+                        Some(self.query_limit),
+                        false, // 按升序排列
+                    )
+                    .await;
+                METRICS.observe_rpc("query_events", started, &page);
+                let page = page?;
+                pages += 1;
+
+                let mut reached_end = !page.has_next_page;
+                let mut events = Vec::with_capacity(page.data.len());
+                for event in page.data {
+                    if range
+                        .to_timestamp_ms
+                        .zip(event.timestamp_ms)
+                        .is_some_and(|(to, ts)| ts > to)
+                    {
+                        reached_end = true;
+                        break;
+                    }
+
+                    let is_last = range.to_cursor == Some(event.id);
+                    let before_range = range
+                        .from_timestamp_ms
+                        .zip(event.timestamp_ms)
+                        .is_some_and(|(from, ts)| ts < from);
+                    if !before_range {
+                        events.push(event);
+                    }
+
+                    if is_last {
+                        reached_end = true;
+                        break;
+                    }
+                }
+
+                debug!("Fetched backfill page {} with {} events", pages, events.len());
+                cursor = page.next_cursor;
+
+                // 写库任务已退出时停止查询，错误由写库任务返回
+                if sender.send(events).await.is_err() || reached_end || cursor.is_none() {
+                    break;
+                }
+            }
+
+            Ok::<_, anyhow::Error>((pages, interrupted))
+        };
+
+        // 写库任务：解析事件并分批写入
+        let write = async {
+            // 接收端随写库任务结束而释放，查询任务发送失败后随之停止
+            let mut receiver = receiver;
+            let mut result = BackfillResult::default();
+            let mut last_event_id = None;
+
+            while let Some(events) = receiver.recv().await {
+                if shutdown.is_requested() {
+                    result.interrupted = true;
+                    break;
+                }
+                result.fetched += events.len();
+                if let Some(event) = events.last() {
+                    last_event_id = Some(event.id);
+                }
+
+                let mut run: Vec<BackfillItem> = Vec::new();
+                for event in &events {
+                    let Some(event_type) = EventType::from_name(event.type_.name.as_str()) else {
+                        debug!("Skipping unindexed event type {}: {:?}", event.type_.name, event.id);
+                        continue;
+                    };
+
+                    let indexed_event = IndexedEvent::from(event);
+                    let sink_event = match self.decode_event(&indexed_event, &event_type) {
+                        Ok(decoded) => SinkEvent::new(&indexed_event, &event_type, decoded),
                         Err(e) => {
-                            error!("Error during polling: {}", e);
-                            // 发生错误时短暂等待后重试
-                            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                            self.record_backfill_failure(&indexed_event, &event_type, &e)
+                                .await?;
+                            result.failed += 1;
+                            continue;
                         }
+                    };
+
+                    // 遇到不同类型的事件或批次已满时先写入当前批次，保证链上顺序
+                    let same_kind = run.last().is_none_or(|item| {
+                        std::mem::discriminant(&item.sink_event.event)
+                            == std::mem::discriminant(&sink_event.event)
+                    });
+                    if !same_kind || run.len() >= self.batch_size {
+                        self.save_backfill_run(std::mem::take(&mut run), &mut result)
+                            .await?;
                     }
+
+                    run.push(BackfillItem {
+                        event: indexed_event,
+                        event_type,
+                        sink_event,
+                    });
                 }
 
-                // 监听 Ctrl+C 信号
-                _ = &mut ctrl_c => {
-                    info!("Received shutdown signal, stopping polling");
-                    break;
+                if !run.is_empty() {
+                    self.save_backfill_run(run, &mut result).await?;
                 }
+
+                info!(
+                    "Backfill progress for {}: {} fetched, {} saved, {} duplicates, {} failed",
+                    cursor_id, result.fetched, result.saved, result.duplicates, result.failed
+                );
             }
 
-            info!("finish...");
+            Ok::<_, anyhow::Error>((result, last_event_id))
+        };
+
+        let ((pages, fetch_interrupted), (mut result, last_event_id)) =
+            tokio::try_join!(fetch, write)?;
+        result.pages = pages;
+        result.interrupted |= fetch_interrupted;
+
+        // 从头回填且尚无游标时，让轮询从回填结束处继续
+        let from_beginning = range.from_cursor.is_none() && range.from_timestamp_ms.is_none();
+        if let Some(event_id) = last_event_id
+            && from_beginning
+            && self.get_latest_cursor(&cursor_id).await?.is_none()
+        {
+            self.update_cursor(
+                &cursor_id,
+                &event_id.tx_digest.to_string(),
+                &event_id.event_seq.to_string(),
+            )
+            .await?;
+            info!("Initialized cursor for {} at {:?}", cursor_id, event_id);
+        }
+
+        info!(
+            "Backfill {} for {}: {} pages, {} fetched, {} saved, {} duplicates, {} failed",
+            if result.interrupted { "interrupted" } else { "finished" },
+            cursor_id, result.pages, result.fetched, result.saved, result.duplicates, result.failed
+        );
+        if result.saved > 0 {
+            self.refresh_platform_stats().await;
         }
+        Ok(result)
+    }
+
+    /// 刷新平台统计，失败只记录日志，不影响事件索引
+    async fn refresh_platform_stats(&self) {
+        match self.db.refresh_platform_stats().await {
+            Ok(()) => debug!("Refreshed platform stats"),
+            Err(e) => warn!("Failed to refresh platform stats: {}", e),
+        }
+    }
+
+    /// 写入一批连续的同类型事件，批量写入失败时逐条写入，失败的事件记入 event_failures
+    async fn save_backfill_run(
+        &self,
+        run: Vec<BackfillItem>,
+        result: &mut BackfillResult,
+    ) -> Result<()> {
+        let Some(first) = run.first() else {
+            return Ok(());
+        };
+
+        let events: Vec<SinkEvent> = run.iter().map(|item| item.sink_event.clone()).collect();
+        match self.sink.write_batch(&events).await {
+            Ok(count) => {
+                result.saved += count;
+                result.duplicates += run.len() as u64 - count;
+                return Ok(());
+            }
+            Err(e) => {
+                warn!(
+                    "Batch write of {} {} events failed, retrying one by one: {}",
+                    run.len(), first.event_type.name(), e
+                );
+            }
+        }
+
+        for item in &run {
+            match self.sink.write(&item.sink_event, None).await {
+                Ok(_) => result.saved += 1,
+                Err(e) => {
+                    self.record_backfill_failure(&item.event, &item.event_type, &e)
+                        .await?;
+                    result.failed += 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // 回填失败的事件写入 event_failures，之后可通过 replay 重放
+    async fn record_backfill_failure(
+        &self,
+        event: &IndexedEvent,
+        event_type: &EventType,
+        error: &anyhow::Error,
+    ) -> Result<()> {
+        let failure = NewEventFailure {
+            tx_digest: event.id.tx_digest.to_string(),
+            event_seq: event.id.event_seq as i64,
+            event_type: event_type.name().to_string(),
+            parsed_json: event.parsed_json.clone(),
+            error: error.to_string(),
+            attempts: 1,
+            timestamp_ms: event.timestamp_ms.map(|ts| ts as i64),
+        };
+        self.db.save_event_failure(&failure, None).await?;
+        warn!(
+            "Moved {} event {:?} to event_failures during backfill: {}",
+            event_type.name(), event.id, error
+        );
         Ok(())
     }
 
@@ -273,405 +886,240 @@ impl BuckyBankIndexer {
         Ok(())
     }
 
-    fn matches_event_type(&self, event: &SuiEvent, event_type: &EventType) -> bool {
-        // 检查事件类型
-        let type_name = &event.type_.name;
-        match event_type {
-            EventType::BuckyBankCreated => type_name.as_str() == "BuckyBankCreated",
-            EventType::DepositMade => type_name.as_str() == "DepositMade", 
-            EventType::WithdrawalRequested => type_name.as_str() == "EventWithdrawalRequested",
-            EventType::WithdrawalApproved => type_name.as_str() == "EventWithdrawalApproved",
-            EventType::WithdrawalRejected => type_name.as_str() == "EventWithdrawalRejected",
-            EventType::EventWithdrawed => type_name.as_str() == "EventWithdrawed",
-        }
-    }
-
-    async fn process_event(&self, event: &SuiEvent, event_type: &EventType) -> Result<()> {
-        match event_type {
-            EventType::BuckyBankCreated => self.process_bucky_bank_created_event(event).await,
-            EventType::DepositMade => self.process_deposit_made_event(event).await,
-            EventType::WithdrawalRequested => self.process_withdrawal_requested_event(event).await,
-            EventType::WithdrawalApproved => self.process_withdrawal_approved_event(event).await,
-            EventType::WithdrawalRejected => self.process_withdrawal_rejected_event(event).await,
-            EventType::EventWithdrawed => self.process_event_withdrawed_event(event).await,
-        }
-    }
+    async fn process_event(
+        &self,
+        event: &IndexedEvent,
+        event_type: &EventType,
+        cursor: Option<&NewCursor>,
+    ) -> Result<()> {
+        debug!("Processing {} event: {:?}", event_type.name(), event.id);
 
-    async fn process_bucky_bank_created_event(&self, event: &SuiEvent) -> Result<()> {
-        debug!("Processing BuckyBankCreated event: {:?}", event.id);
-
-        let parsed_data = &event.parsed_json;
-
-        tracing::info!("Parsed data: {:?}", parsed_data);
-
-        let bucky_bank_id = parsed_data
-            .get("bucky_bank_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing bucky_bank_id"))?;
-
-        let name = parsed_data
-            .get("name")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing bucky bank name"))?;
-
-        let parent_address = parsed_data
-            .get("parent")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing parent_address"))?;
-
-        let child_address = parsed_data
-            .get("child")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing child_address"))?;
-
-        let target_amount = parsed_data
-            .get("target_amount")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<u64>().ok())
-            .ok_or_else(|| anyhow::anyhow!("Missing or invalid target_amount"))?;
-
-        let created_at_ms = parsed_data
-            .get("created_at_ms")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<u64>().ok())
-            .ok_or_else(|| anyhow::anyhow!("Missing or invalid created_at_ms"))?;
-
-        let deadline_ms = parsed_data
-            .get("deadline_ms")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<u64>().ok())
-            .ok_or_else(|| anyhow::anyhow!("Missing or invalid deadline_ms"))?;
-
-        let duration_days = parsed_data
-            .get("duration_days")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<u64>().ok())
-            .ok_or_else(|| anyhow::anyhow!("Missing or invalid duration_days"))?;
-
-        let current_balance = parsed_data
-            .get("current_balance")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<u64>().ok())
-            .ok_or_else(|| anyhow::anyhow!("Missing or invalid current_balance"))?;
-
-        let new_event = NewBuckyBankCreatedEvent {
-            bucky_bank_id: bucky_bank_id.to_string(),
-            name: name.to_string(),
-            parent_address: parent_address.to_string(),
-            child_address: child_address.to_string(),
-            target_amount: target_amount as i64,
-            created_at_ms: created_at_ms as i64,
-            deadline_ms: deadline_ms as i64,
-            duration_days: duration_days as i64,
-            current_balance: current_balance as i64,
+        let started = Instant::now();
+        let saved = match self.decode_event(event, event_type) {
+            Ok(decoded) => self.write_event(event, event_type, decoded, cursor).await,
+            Err(e) => Err(e),
         };
+        METRICS.observe_event(event_type.name(), started, event.timestamp_ms, saved.is_ok());
+        if saved.is_ok() {
+            self.state.record_event_timestamp(event.timestamp_ms);
+        }
 
-        match self.db.save_bucky_bank_created_event(&new_event).await {
-            Ok(saved_event) => {
-                info!("Saved BuckyBankCreated event: {}", saved_event.id);
-                Ok(())
-            }
-            Err(e) => {
-                error!("Failed to save BuckyBankCreated event: {}", e);
-                Err(e)
-            }
+        if let Some(notification) = saved? {
+            self.publish_event(&notification).await;
         }
+        Ok(())
     }
 
-    async fn process_deposit_made_event(&self, event: &SuiEvent) -> Result<()> {
-        debug!("Processing DepositMade event: {:?}", event.id);
-
-        let parsed_data = &event.parsed_json;
-
-        tracing::info!("Parsed data: {:?}", parsed_data);
-
-        let bucky_bank_id = parsed_data
-            .get("bucky_bank_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing bucky_bank_id"))?;
-
-        let amount = parsed_data
-            .get("amount")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<u64>().ok())
-            .ok_or_else(|| anyhow::anyhow!("Missing or invalid amount"))?;
-
-        let depositor = parsed_data
-            .get("depositor")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing depositor"))?;
-
-        let created_at_ms = parsed_data
-            .get("created_at_ms")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<u64>().ok())
-            .ok_or_else(|| anyhow::anyhow!("Missing or invalid created_at_ms"))?;
+    /// 生成事件通知并与事件一起交给输出端写入，返回新写入事件的通知
+    ///
+    /// webhook 投递记录由 Postgres 输出端在事件写入的事务内生成，写入失败时一并回滚，随事件重试。
+    async fn write_event(
+        &self,
+        event: &IndexedEvent,
+        event_type: &EventType,
+        decoded: BuckyBankEvent,
+        cursor: Option<&NewCursor>,
+    ) -> Result<Option<EventNotification>> {
+        let notification = self.build_notification(event, event_type, &decoded).await?;
+        let sink_event = SinkEvent::new(event, event_type, decoded).with_notification(notification);
+        let saved = self.sink.write(&sink_event, cursor).await?;
+        Ok(sink_event.notification.filter(|_| saved))
+    }
 
-        let new_event = NewDepositMadeEvent {
-            bucky_bank_id: bucky_bank_id.to_string(),
-            amount: amount as i64,
-            depositor: depositor.to_string(),
-            created_at_ms: created_at_ms as i64,
+    /// 生成事件通知，查询失败时返回错误，事件按失败处理并重试
+    async fn build_notification(
+        &self,
+        event: &IndexedEvent,
+        event_type: &EventType,
+        decoded: &BuckyBankEvent,
+    ) -> Result<EventNotification> {
+        let (bucky_bank_id, actor) = decoded.subject();
+
+        // 通知中带上家长和孩子地址，订阅者可以按地址接收整个存钱罐的动态
+        let mut addresses = match self.bank_addresses(bucky_bank_id, decoded).await? {
+            Some((parent_address, child_address)) => vec![parent_address, child_address],
+            None => Vec::new(),
         };
-
-        match self.db.save_deposit_made_event(&new_event).await {
-            Ok(saved_event) => {
-                info!("Saved DepositMade event: {}", saved_event.id);
-                Ok(())
-            }
-            Err(e) => {
-                error!("Failed to save DepositMade event: {}", e);
-                Err(e)
-            }
+        if !addresses.iter().any(|address| address == actor) {
+            addresses.push(actor.to_string());
         }
+
+        Ok(EventNotification {
+            event_type: event_type.name().to_string(),
+            bucky_bank_id: bucky_bank_id.to_string(),
+            addresses,
+            tx_digest: event.id.tx_digest.to_string(),
+            event_seq: event.id.event_seq as i64,
+            timestamp_ms: event.timestamp_ms.map(|ts| ts as i64),
+            data: event.parsed_json.clone(),
+        })
     }
 
-    async fn process_withdrawal_requested_event(&self, event: &SuiEvent) -> Result<()> {
-        debug!("Processing EventWithdrawalRequested event: {:?}", event.id);
-
-        let parsed_data = &event.parsed_json;
-
-        tracing::info!("Parsed data: {:?}", parsed_data);
-
-        let request_id = parsed_data
-            .get("request_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing request_id"))?;
-
-        let bucky_bank_id = parsed_data
-            .get("bucky_bank_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing bucky_bank_id"))?;
-
-        let amount = parsed_data
-            .get("amount")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<u64>().ok())
-            .ok_or_else(|| anyhow::anyhow!("Missing or invalid amount"))?;
-
-        let requester = parsed_data
-            .get("requester")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing requester"))?;
-
-        let reason = parsed_data
-            .get("reason")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing reason"))?;
-
-        //  INFO  Parsed data: Object {"amount": String("3000000"), "approved_by": String("0x27b2306354b0537a9ac9eddb26e10f327d6ef660333902e313b1bcb4353c5d3f"), "bucky_bank_id": String("0xd7dd42e481af321a09533e727dd53c0ee58ef2257333e3471358254363c3c3de"), "created_at_ms": String("1760166241636"), "reason": String("I need it"), "request_id": String("0x8048af51a7c16e4a0bd8555c8147e5e2e7a0b6ca59499d8ede96aea24db44b8d"), "requester": String("0x27b2306354b0537a9ac9eddb26e10f327d6ef660333902e313b1bcb4353c5d3f"), "status": Object {"variant": String("Pending"), "fields": Object {}}}
-
-        // 处理枚举类型的status字段
-        let status = if let Some(status_obj) = parsed_data.get("status") {
-            if let Some(variant) = status_obj.get("variant").and_then(|v| v.as_str()) {
-                match variant {
-                    "Pending" => WithdrawalStatus::Pending,
-                    "Approved" => WithdrawalStatus::Approved,
-                    "Rejected" => WithdrawalStatus::Rejected,
-                    "Cancelled" => WithdrawalStatus::Cancelled,
-                    "Withdrawed" => WithdrawalStatus::Withdrawed,
-                    _ => return Err(anyhow::anyhow!("Unknown status variant: {}", variant)),
+    /// 查找存钱罐的家长和孩子地址：创建事件直接使用事件中的地址，其他事件先查缓存再查数据库
+    async fn bank_addresses(
+        &self,
+        bucky_bank_id: &str,
+        decoded: &BuckyBankEvent,
+    ) -> Result<Option<(String, String)>> {
+        let addresses = match decoded {
+            BuckyBankEvent::BuckyBankCreated(created) => {
+                (created.parent_address.clone(), created.child_address.clone())
+            }
+            _ => {
+                if let Some(addresses) = self.bank_addresses.lock().unwrap().get(bucky_bank_id) {
+                    return Ok(Some(addresses.clone()));
                 }
-            } else if let Some(status_str) = status_obj.as_str() {
-                // 兼容简单字符串格式
-                match status_str {
-                    "Pending" => WithdrawalStatus::Pending,
-                    "Approved" => WithdrawalStatus::Approved,
-                    "Rejected" => WithdrawalStatus::Rejected,
-                    "Cancelled" => WithdrawalStatus::Cancelled,
-                    "Withdrawed" => WithdrawalStatus::Withdrawed,
-                    _ => return Err(anyhow::anyhow!("Unknown status: {}", status_str)),
+                match self.db.get_bucky_bank_addresses(bucky_bank_id).await? {
+                    Some(addresses) => addresses,
+                    None => return Ok(None),
                 }
-            } else {
-                return Err(anyhow::anyhow!("Invalid status format"));
             }
-        } else {
-            return Err(anyhow::anyhow!("Missing status"));
         };
 
-        let approved_by = parsed_data
-            .get("approved_by")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string());
-
-        let created_at_ms = parsed_data
-            .get("created_at_ms")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<u64>().ok())
-            .ok_or_else(|| anyhow::anyhow!("Missing or invalid created_at_ms"))?;
+        self.bank_addresses
+            .lock()
+            .unwrap()
+            .insert(bucky_bank_id.to_string(), addresses.clone());
+        Ok(Some(addresses))
+    }
 
-        let new_event = NewWithdrawalRequestEvent {
-            request_id: request_id.to_string(),
-            bucky_bank_id: bucky_bank_id.to_string(),
-            amount: amount as i64,
-            requester: requester.to_string(),
-            reason: reason.to_string(),
-            status: status.to_string(), // 转换为字符串
-            approved_by: approved_by.unwrap_or_default(), // 使用unwrap_or_default处理Option
-            created_at_ms: created_at_ms as i64,
-            audit_at_ms: None, // 初始化为None，审批事件会更新这个字段
-            tx_digest: event.id.tx_digest.to_string(),
-            event_seq: event.id.event_seq as i64,
-            timestamp_ms: event.timestamp_ms.unwrap_or(0) as i64,
-        };
+    /// 事件写入提交后发布实时通知，失败只记录日志；回填的批量写入不发布通知
+    async fn publish_event(&self, notification: &EventNotification) {
+        match self.db.notify_event(notification).await {
+            Ok(()) => debug!(
+                "Published {} notification for {}:{}",
+                notification.event_type, notification.tx_digest, notification.event_seq
+            ),
+            Err(e) => warn!("Failed to publish {} notification: {}", notification.event_type, e),
+        }
+    }
 
-        match self.db.save_withdrawal_request_event(&new_event).await {
-            Ok(saved_event) => {
-                info!("Saved EventWithdrawalRequested event: {}", saved_event.id);
-                Ok(())
-            }
-            Err(e) => {
-                error!("Failed to save EventWithdrawalRequested event: {}", e);
-                Err(e)
-            }
+    /// 按配置的编码解析事件内容，没有 BCS 字节时（如死信重放）解析 parsed_json
+    fn decode<T: MoveEvent>(&self, event: &IndexedEvent) -> Result<T> {
+        match (self.event_encoding, &event.bcs) {
+            (EventEncoding::Bcs, Some(bytes)) => T::from_bcs(bytes),
+            _ => T::from_json(&event.parsed_json),
         }
     }
 
-    async fn process_withdrawal_approved_event(&self, event: &SuiEvent) -> Result<()> {
-        debug!("Processing EventWithdrawalApproved event: {:?}", event.id);
-
-        let parsed_data = &event.parsed_json;
-        tracing::info!("Parsed data: {:?}", parsed_data);
-
-        let request_id = parsed_data
-            .get("request_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing request_id"))?;
-
-        let approved_by = parsed_data
-            .get("approved_by")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing approved_by"))?;
-
-        let created_at_ms = parsed_data
-            .get("created_at_ms")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<u64>().ok())
-            .ok_or_else(|| anyhow::anyhow!("Missing or invalid created_at_ms"))?;
-
-        // 更新提取请求状态为Approved
-        match self.db.update_withdrawal_request_status(
-            request_id,
-            &WithdrawalStatus::Approved,
-            Some(approved_by),
-            Some(created_at_ms as i64),
-        ).await {
-            Ok(true) => {
-                info!("Successfully approved withdrawal request: {}", request_id);
-                Ok(())
+    /// 按事件类型解析事件内容并转换为数据库模型
+    fn decode_event(&self, event: &IndexedEvent, event_type: &EventType) -> Result<BuckyBankEvent> {
+        let tx_digest = event.id.tx_digest.to_string();
+        let event_seq = event.id.event_seq as i64;
+
+        let decoded = match event_type {
+            EventType::BuckyBankCreated => {
+                let created: events::BuckyBankCreated = self.decode(event)?;
+                BuckyBankEvent::BuckyBankCreated(NewBuckyBankCreatedEvent {
+                    bucky_bank_id: created.bucky_bank_id,
+                    name: created.name,
+                    parent_address: created.parent,
+                    child_address: created.child,
+                    target_amount: created.target_amount as i64,
+                    created_at_ms: created.created_at_ms as i64,
+                    deadline_ms: created.deadline_ms as i64,
+                    duration_days: created.duration_days as i64,
+                    current_balance: created.current_balance as i64,
+                    tx_digest,
+                    event_seq,
+                })
             }
-            Ok(false) => {
-                error!("Withdrawal request not found: {}", request_id);
-                Err(anyhow::anyhow!("Withdrawal request not found: {}", request_id))
+            EventType::DepositMade => {
+                let deposit: events::DepositMade = self.decode(event)?;
+                BuckyBankEvent::DepositMade(NewDepositMadeEvent {
+                    bucky_bank_id: deposit.bucky_bank_id,
+                    amount: deposit.amount as i64,
+                    depositor: deposit.depositor,
+                    created_at_ms: deposit.created_at_ms as i64,
+                    tx_digest,
+                    event_seq,
+                })
             }
-            Err(e) => {
-                error!("Failed to approve withdrawal request: {}", e);
-                Err(e)
+            EventType::WithdrawalRequested => {
+                let request: events::EventWithdrawalRequested = self.decode(event)?;
+                BuckyBankEvent::WithdrawalRequested(NewWithdrawalRequestEvent {
+                    request_id: request.request_id,
+                    bucky_bank_id: request.bucky_bank_id,
+                    amount: request.amount as i64,
+                    requester: request.requester,
+                    reason: request.reason,
+                    status: request.status.to_string(),
+                    approved_by: request.approved_by,
+                    created_at_ms: request.created_at_ms as i64,
+                    audit_at_ms: None, // 审批事件会更新这个字段
+                    tx_digest,
+                    event_seq,
+                    timestamp_ms: event.timestamp_ms.unwrap_or(0) as i64,
+                })
             }
-        }
-    }
-
-    async fn process_withdrawal_rejected_event(&self, event: &SuiEvent) -> Result<()> {
-        debug!("Processing EventWithdrawalRejected event: {:?}", event.id);
-
-        let parsed_data = &event.parsed_json;
-        tracing::info!("Parsed data: {:?}", parsed_data);
-
-        let request_id = parsed_data
-            .get("request_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing request_id"))?;
-
-        let rejected_by = parsed_data
-            .get("rejected_by")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing rejected_by"))?;
-
-        let created_at_ms = parsed_data
-            .get("created_at_ms")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<u64>().ok())
-            .ok_or_else(|| anyhow::anyhow!("Missing or invalid created_at_ms"))?;
-
-        // 更新提取请求状态为Rejected
-        match self.db.update_withdrawal_request_status(
-            request_id,
-            &WithdrawalStatus::Rejected,
-            Some(rejected_by),
-            Some(created_at_ms as i64),
-        ).await {
-            Ok(true) => {
-                info!("Successfully rejected withdrawal request: {}", request_id);
-                Ok(())
+            EventType::WithdrawalApproved => {
+                let approved: events::EventWithdrawalApproved = self.decode(event)?;
+                BuckyBankEvent::WithdrawalAudited(WithdrawalAudit {
+                    request_id: approved.request_id,
+                    bucky_bank_id: approved.bucky_bank_id,
+                    amount: approved.amount as i64,
+                    requester: approved.requester,
+                    reason: approved.reason,
+                    status: WithdrawalStatus::Approved,
+                    audited_by: approved.approved_by,
+                    audit_at_ms: approved.created_at_ms as i64,
+                })
             }
-            Ok(false) => {
-                error!("Withdrawal request not found: {}", request_id);
-                Err(anyhow::anyhow!("Withdrawal request not found: {}", request_id))
+            EventType::WithdrawalRejected => {
+                let rejected: events::EventWithdrawalRejected = self.decode(event)?;
+                BuckyBankEvent::WithdrawalAudited(WithdrawalAudit {
+                    request_id: rejected.request_id,
+                    bucky_bank_id: rejected.bucky_bank_id,
+                    amount: rejected.amount as i64,
+                    requester: rejected.requester,
+                    reason: rejected.reason,
+                    status: WithdrawalStatus::Rejected,
+                    audited_by: rejected.rejected_by,
+                    audit_at_ms: rejected.created_at_ms as i64,
+                })
             }
-            Err(e) => {
-                error!("Failed to reject withdrawal request: {}", e);
-                Err(e)
+            EventType::EventWithdrawed => {
+                let withdrawed: events::EventWithdrawed = self.decode(event)?;
+                BuckyBankEvent::EventWithdrawed(NewEventWithdrawedEvent {
+                    request_id: withdrawed.request_id,
+                    bucky_bank_id: withdrawed.bucky_bank_id,
+                    amount: withdrawed.amount as i64,
+                    left_balance: withdrawed.left_balance as i64,
+                    withdrawer: withdrawed.withdrawer,
+                    created_at_ms: withdrawed.created_at_ms as i64,
+                    tx_digest,
+                    event_seq,
+                })
             }
-        }
-    }
-
-    async fn process_event_withdrawed_event(&self, event: &SuiEvent) -> Result<()> {
-        debug!("Processing EventWithdrawed event: {:?}", event.id);
-
-        let parsed_data = &event.parsed_json;
-        tracing::info!("Parsed data: {:?}", parsed_data);
-
-        let request_id = parsed_data
-            .get("request_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing request_id"))?;
-
-        let bucky_bank_id = parsed_data
-            .get("bucky_bank_id")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing bucky_bank_id"))?;
-
-        let amount = parsed_data
-            .get("amount")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<u64>().ok())
-            .ok_or_else(|| anyhow::anyhow!("Missing or invalid amount"))?;
-
-        let left_balance = parsed_data
-            .get("left_balance")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<u64>().ok())
-            .ok_or_else(|| anyhow::anyhow!("Missing or invalid left_balance"))?;
-
-        let withdrawer = parsed_data
-            .get("withdrawer")
-            .and_then(|v| v.as_str())
-            .ok_or_else(|| anyhow::anyhow!("Missing withdrawer"))?;
-
-        let created_at_ms = parsed_data
-            .get("created_at_ms")
-            .and_then(|v| v.as_str())
-            .and_then(|s| s.parse::<u64>().ok())
-            .ok_or_else(|| anyhow::anyhow!("Missing or invalid created_at_ms"))?;
-
-        let new_event = NewEventWithdrawedEvent {
-            request_id: request_id.to_string(),
-            bucky_bank_id: bucky_bank_id.to_string(),
-            amount: amount as i64,
-            left_balance: left_balance as i64,
-            withdrawer: withdrawer.to_string(),
-            created_at_ms: created_at_ms as i64,
-        };
-
-        match self.db.save_event_withdrawed_event(&new_event).await {
-            Ok(saved_event) => {
-                info!("Saved EventWithdrawed event: {}", saved_event.id);
-                Ok(())
+            EventType::BankReward => {
+                let reward: events::EventBankReward = self.decode(event)?;
+                // EventBankReward 本身不带时间字段，使用链上事件时间戳
+                BuckyBankEvent::BankReward(NewBankRewardEvent {
+                    bucky_bank_id: reward.bank_id,
+                    banks_count: reward.banks_count as i64,
+                    reward_receiver: reward.reward_receiver,
+                    total_reward: reward.total_reward as i64,
+                    bank_reward: reward.bank_reward as i64,
+                    total_balance: reward.total_balance as i64,
+                    bank_balance: reward.bank_balance as i64,
+                    tx_digest,
+                    event_seq,
+                    timestamp_ms: event.timestamp_ms.unwrap_or(0) as i64,
+                })
             }
-            Err(e) => {
-                error!("Failed to save EventWithdrawed event: {}", e);
-                Err(e)
+            EventType::ClaimReward => {
+                let claim: events::EventClaimReward = self.decode(event)?;
+                BuckyBankEvent::ClaimReward(NewRewardClaimEvent {
+                    bucky_bank_id: claim.bank_id,
+                    reward_receiver: claim.reward_receiver,
+                    reward_amount: claim.reward_amount as i64,
+                    created_at_ms: claim.created_at_ms as i64,
+                    tx_digest,
+                    event_seq,
+                })
             }
-        }
+        };
+
+        Ok(decoded)
     }
 }
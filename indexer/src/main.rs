@@ -1,22 +1,37 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use std::sync::Arc;
-use sui_sdk::SuiClientBuilder;
-use tokio::signal;
-use tracing::{Instrument, error, info, info_span};
+use tracing::{Instrument, error, info, info_span, warn};
 
+mod admin;
+mod auth;
+mod chain;
 mod config;
 mod database;
+mod events;
 mod handlers;
 mod health;
 mod indexer;
 mod logging;
-
+mod metrics;
+mod shutdown;
+mod sink;
+mod state;
+mod supervisor;
+mod verifier;
+mod webhook;
+
+use chain::ChainServices;
 use config::Config;
 use database::Database;
 use health::{HealthState, health_routes};
-use indexer::BuckyBankIndexer;
+use indexer::{BackfillRange, BuckyBankIndexer, parse_event_cursor};
+use state::IndexerState;
 use logging::init_logging;
+use shutdown::Shutdown;
+use supervisor::IndexerSupervisor;
+use verifier::StateVerifier;
+use webhook::WebhookDispatcher;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -43,6 +58,55 @@ enum Commands {
     Run,
     /// 初始化数据库
     InitDb,
+    /// 重放 event_failures 中处理失败的事件
+    Replay {
+        /// 只重放指定ID的失败事件
+        #[arg(long)]
+        id: Option<uuid::Uuid>,
+
+        /// 单次最多重放的事件数量
+        #[arg(long, default_value_t = 100)]
+        limit: i64,
+    },
+    /// 按游标或时间范围回填历史事件
+    Backfill {
+        /// 从该事件之后开始回填，格式为 tx_digest:event_seq
+        #[arg(long)]
+        from_cursor: Option<String>,
+
+        /// 回填到该事件为止（包含），格式为 tx_digest:event_seq
+        #[arg(long)]
+        to_cursor: Option<String>,
+
+        /// 起始时间戳(毫秒)
+        #[arg(long)]
+        from_timestamp_ms: Option<u64>,
+
+        /// 结束时间戳(毫秒)
+        #[arg(long)]
+        to_timestamp_ms: Option<u64>,
+    },
+    /// 按存取款流水核对BuckyBank余额
+    Reconcile {
+        /// 只核对指定的BuckyBank
+        #[arg(long)]
+        bucky_bank_id: Option<String>,
+
+        /// 修正记录的余额，不指定时只报告偏差
+        #[arg(long)]
+        fix: bool,
+    },
+    /// 创建管理接口 API Key，明文只输出一次
+    CreateAdminKey {
+        /// 使用者名称，写入人工修正审计记录
+        #[arg(long)]
+        name: String,
+    },
+    /// 吊销管理接口 API Key
+    RevokeAdminKey {
+        #[arg(long)]
+        name: String,
+    },
 }
 
 #[tokio::main]
@@ -53,13 +117,13 @@ async fn main() -> Result<()> {
     let cli = Cli::parse();
 
     // 加载配置
-    let config = if let Some(config_path) = cli.config {
+    let config = Arc::new(if let Some(config_path) = cli.config {
         info!("Loading configuration from {}", config_path);
         Config::load(Some(config_path))?
     } else {
         info!("Loading development configuration");
         Config::development()
-    };
+    });
 
     info!("Configuration loaded, db: {}", config.database.url);
 
@@ -80,59 +144,117 @@ async fn main() -> Result<()> {
             info!("Initializing database");
             init_database(&config).await?;
         }
+        Commands::Replay { id, limit } => {
+            info!("Replaying failed events");
+            replay_failures(&config, id, limit).await?;
+        }
+        Commands::Backfill {
+            from_cursor,
+            to_cursor,
+            from_timestamp_ms,
+            to_timestamp_ms,
+        } => {
+            info!("Starting backfill mode");
+            let range = BackfillRange {
+                from_cursor: from_cursor.as_deref().map(parse_event_cursor).transpose()?,
+                to_cursor: to_cursor.as_deref().map(parse_event_cursor).transpose()?,
+                from_timestamp_ms,
+                to_timestamp_ms,
+            };
+            backfill(&config, range).await?;
+        }
+        Commands::Reconcile { bucky_bank_id, fix } => {
+            info!("Reconciling BuckyBank balances");
+            reconcile(&config, bucky_bank_id.as_deref(), fix).await?;
+        }
+        Commands::CreateAdminKey { name } => {
+            create_admin_key(&config, &name).await?;
+        }
+        Commands::RevokeAdminKey { name } => {
+            revoke_admin_key(&config, &name).await?;
+        }
     }
 
     Ok(())
 }
 
-async fn run_indexer(config: &Config) -> Result<()> {
-    // 初始化数据库连接
+/// polling_state 只在同进程内运行轮询时传入，/health 和 /ready 会带上轮询状态
+fn build_health_state(
+    config: &Config,
+    db: Arc<Database>,
+    chain: Arc<ChainServices>,
+    polling_state: Option<Arc<IndexerState>>,
+    shutdown: &Shutdown,
+) -> HealthState {
+    let verifier = Arc::new(StateVerifier::new(
+        chain.clone(),
+        db.clone(),
+        config.indexing.verify_interval_seconds,
+    ));
+
+    // 后台定期核对链上状态
+    let periodic_verifier = verifier.clone();
+    let verifier_shutdown = shutdown.clone();
+    tokio::spawn(async move { periodic_verifier.run_periodic(verifier_shutdown).await });
+
+    HealthState {
+        db,
+        polling_state,
+        chain,
+        verifier,
+        shutdown: shutdown.clone(),
+        start_time: std::time::Instant::now(),
+    }
+}
+
+async fn run_indexer(config: &Arc<Config>) -> Result<()> {
+    let shutdown = Shutdown::listen();
     let db = Arc::new(Database::new(&config.database).await?);
+    let event_indexer = ChainServices::new(config.clone(), db.clone()).indexer().await?;
 
-    // 初始化SUI客户端
-    let sui_client = Arc::new(
-        SuiClientBuilder::default()
-            .build(&config.sui.rpc_url)
-            .await?,
-    );
+    supervise_indexer(config, db, event_indexer, shutdown).await
+}
 
-    // 创建事件索引器
-    let event_indexer = BuckyBankIndexer::new(
-        sui_client,
-        config.sui.package_id.clone(),
-        config.sui.module_name.clone(),
-        db,
-    );
+/// 启动 webhook 投递任务，并在监督下持续轮询直到收到关闭信号
+async fn supervise_indexer(
+    config: &Config,
+    db: Arc<Database>,
+    event_indexer: Arc<BuckyBankIndexer>,
+    shutdown: Shutdown,
+) -> Result<()> {
+    let dispatcher_handle = if config.webhook.enabled {
+        let dispatcher = WebhookDispatcher::new(db, &config.webhook)?;
+        let dispatcher_shutdown = shutdown.clone();
+        Some(tokio::spawn(
+            async move { dispatcher.run(dispatcher_shutdown).await }
+                .instrument(info_span!("webhook_dispatcher")),
+        ))
+    } else {
+        None
+    };
 
-    // 启动持续轮询
-    let span = info_span!("continuous_polling");
-    tokio::select! {
-        result = event_indexer.run_continuous_polling().instrument(span) => {
-            match result {
-                Ok(_) => {
-                    info!("Polling completed normally");
-                    Ok(())
-                }
-                Err(e) => {
-                    error!("Polling failed: {}", e);
-                    Err(e)
-                }
-            }
-        }
-        _ = tokio::signal::ctrl_c() => {
-            info!("Received Ctrl+C, shutting down gracefully");
-            Ok(())
-        }
+    let result = IndexerSupervisor::new(event_indexer, shutdown.clone(), &config.indexing)
+        .run()
+        .await;
+
+    // 索引器被 halt 时不等待关闭信号，投递任务继续运行到进程退出
+    if shutdown.is_requested()
+        && let Some(handle) = dispatcher_handle
+        && let Err(e) = handle.await
+    {
+        error!("Webhook dispatcher task failed: {}", e);
     }
+
+    result
 }
 
-async fn run_server(config: &Config) -> Result<()> {
+async fn run_server(config: &Arc<Config>) -> Result<()> {
+    let shutdown = Shutdown::listen();
     let db = Arc::new(Database::new(&config.database).await?);
+    // 只提供 HTTP 服务，Sui 客户端和索引器在调用重放或核对接口时才创建
+    let chain = Arc::new(ChainServices::new(config.clone(), db.clone()));
 
-    let health_state = HealthState {
-        db: db.clone(),
-        start_time: std::time::Instant::now(),
-    };
+    let health_state = build_health_state(config, db, chain, None, &shutdown);
 
     let app = health_routes(health_state);
 
@@ -142,21 +264,28 @@ async fn run_server(config: &Config) -> Result<()> {
     info!("Health check server listening on {}", addr);
 
     axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
+        .with_graceful_shutdown(async move { shutdown.requested().await })
         .await?;
 
     Ok(())
 }
 
-async fn run_both(config: Config) -> Result<()> {
-    let config = Arc::new(config);
+async fn run_both(config: Arc<Config>) -> Result<()> {
+    let shutdown = Shutdown::listen();
+
+    // HTTP 服务、轮询、核对和 webhook 投递共用同一个连接池、Sui 客户端和索引器
     let db = Arc::new(Database::new(&config.database).await?);
+    let chain = Arc::new(ChainServices::new(config.clone(), db.clone()));
+    let event_indexer = chain.indexer().await?;
 
     // 启动健康检查服务器
-    let health_state = HealthState {
-        db: db.clone(),
-        start_time: std::time::Instant::now(),
-    };
+    let health_state = build_health_state(
+        &config,
+        db.clone(),
+        chain,
+        Some(event_indexer.state()),
+        &shutdown,
+    );
 
     let app = health_routes(health_state);
 
@@ -165,31 +294,33 @@ async fn run_both(config: Config) -> Result<()> {
 
     info!("Health check server listening on {}", addr);
 
+    let server_shutdown = shutdown.clone();
     let server_handle = tokio::spawn(async move {
         axum::serve(listener, app)
-            .with_graceful_shutdown(shutdown_signal())
+            .with_graceful_shutdown(async move { server_shutdown.requested().await })
             .await
     });
 
-    // 启动索引器
+    // 启动受监督的索引器，轮询异常退出时自动重启，HTTP 服务不受影响
     let config_clone = config.clone();
-    let indexer_handle = tokio::spawn(async move { run_indexer(&config_clone).await });
+    let indexer_handle = tokio::spawn(async move {
+        supervise_indexer(&config_clone, db, event_indexer, shutdown).await
+    });
 
-    // 等待其中一个任务完成
-    tokio::select! {
-        result = server_handle => {
-            if let Err(e) = result {
-                error!("Server task failed: {}", e);
-            }
-        }
-        result = indexer_handle => {
-            if let Err(e) = result {
-                error!("Indexer task failed: {}", e);
-            }
+    // HTTP 服务只在收到关闭信号后退出，等待轮询处理完当前一页
+    let (server_result, indexer_result) = tokio::join!(server_handle, indexer_handle);
+    match server_result {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => error!("Server failed: {}", e),
+        Err(e) => error!("Server task failed: {}", e),
+    }
+    match indexer_result {
+        Ok(result) => result,
+        Err(e) => {
+            error!("Indexer task failed: {}", e);
+            Err(e.into())
         }
     }
-
-    Ok(())
 }
 
 async fn init_database(config: &Config) -> Result<()> {
@@ -203,30 +334,94 @@ async fn init_database(config: &Config) -> Result<()> {
     Ok(())
 }
 
-async fn shutdown_signal() {
-    let ctrl_c = async {
-        signal::ctrl_c()
-            .await
-            .expect("Failed to install Ctrl+C handler");
-    };
+async fn replay_failures(config: &Arc<Config>, id: Option<uuid::Uuid>, limit: i64) -> Result<()> {
+    let shutdown = Shutdown::listen();
+    let db = Arc::new(Database::new(&config.database).await?);
+    let event_indexer = ChainServices::new(config.clone(), db.clone()).indexer().await?;
+
+    match id {
+        Some(id) => {
+            let failure = db
+                .get_event_failure_by_id(id)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Event failure not found: {}", id))?;
+
+            if event_indexer.replay_failure(&failure).await? {
+                info!("Event failure {} replayed successfully", id);
+            } else {
+                error!("Event failure {} failed again", id);
+            }
+        }
+        None => {
+            let result = event_indexer.replay_failures(limit, &shutdown).await?;
+            info!(
+                "Replay finished: {} replayed, {} resolved, {} still failing",
+                result.replayed, result.resolved, result.failed
+            );
+        }
+    }
 
-    #[cfg(unix)]
-    let terminate = async {
-        signal::unix::signal(signal::unix::SignalKind::terminate())
-            .expect("Failed to install signal handler")
-            .recv()
-            .await;
-    };
+    Ok(())
+}
 
-    #[cfg(not(unix))]
-    let terminate = std::future::pending::<()>();
+async fn backfill(config: &Arc<Config>, range: BackfillRange) -> Result<()> {
+    let shutdown = Shutdown::listen();
+    let db = Arc::new(Database::new(&config.database).await?);
+    let event_indexer = ChainServices::new(config.clone(), db.clone()).indexer().await?;
 
-    tokio::select! {
-        _ = ctrl_c => {
-            info!("Received Ctrl+C, shutting down");
-        }
-        _ = terminate => {
-            info!("Received terminate signal, shutting down");
-        }
+    // 收到关闭信号时回填在页之间停止，已写入的页保留
+    let result = event_indexer.backfill(&range, &shutdown).await?;
+    if result.interrupted {
+        warn!("Backfill stopped by shutdown signal before reaching the end of the range");
+    }
+
+    Ok(())
+}
+
+async fn reconcile(config: &Config, bucky_bank_id: Option<&str>, fix: bool) -> Result<()> {
+    let db = Database::new(&config.database).await?;
+    let report = db.reconcile_bucky_bank_balances(bucky_bank_id, fix).await?;
+
+    for drift in &report.drifted {
+        warn!(
+            "Balance drift for {}: stored={}, deposits-withdrawals={}, latest_left_balance={:?}, expected_from_withdrawal={:?}",
+            drift.bucky_bank_id,
+            drift.stored_balance,
+            drift.computed_balance,
+            drift.latest_left_balance,
+            drift.expected_balance
+        );
+    }
+
+    info!(
+        "Reconcile finished: {} checked, {} drifted, {} fixed",
+        report.checked,
+        report.drifted.len(),
+        report.fixed
+    );
+
+    Ok(())
+}
+
+async fn create_admin_key(config: &Config, name: &str) -> Result<()> {
+    let db = Database::new(&config.database).await?;
+    let key = admin::generate_api_key();
+    let admin_key = db.create_admin_api_key(name, &bucky_bank_api::auth::hash_token(&key)).await?;
+
+    info!("Created admin API key {} for {}", admin_key.id, admin_key.name);
+    // 明文只输出这一次，数据库中只保存摘要
+    println!("{}", key);
+
+    Ok(())
+}
+
+async fn revoke_admin_key(config: &Config, name: &str) -> Result<()> {
+    let db = Database::new(&config.database).await?;
+    if db.revoke_admin_api_key(name).await? {
+        info!("Revoked admin API key for {}", name);
+    } else {
+        warn!("No active admin API key found for {}", name);
     }
+
+    Ok(())
 }
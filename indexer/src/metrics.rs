@@ -0,0 +1,185 @@
+use anyhow::Result;
+use prometheus::{
+    Encoder, Gauge, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+use sqlx::PgPool;
+use std::sync::LazyLock;
+use std::time::Instant;
+
+/// 进程内共享的指标，`run` 模式下轮询任务和 HTTP 服务在同一进程中，`/metrics` 可以读到轮询的计数
+pub static METRICS: LazyLock<IndexerMetrics> = LazyLock::new(IndexerMetrics::new);
+
+// 事件处理耗时分桶(秒)，单个事件通常只有几次数据库写入
+const EVENT_LATENCY_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0];
+
+// RPC 调用耗时分桶(秒)
+const RPC_LATENCY_BUCKETS: &[f64] = &[0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+pub struct IndexerMetrics {
+    registry: Registry,
+    events_processed: IntCounterVec,
+    events_failed: IntCounterVec,
+    event_process_seconds: HistogramVec,
+    rpc_request_seconds: HistogramVec,
+    rpc_errors: IntCounterVec,
+    latest_event_timestamp_ms: IntGauge,
+    indexing_lag_seconds: Gauge,
+    last_caught_up_timestamp_seconds: IntGauge,
+    db_pool_connections: IntGauge,
+    db_pool_idle_connections: IntGauge,
+    db_pool_max_connections: IntGauge,
+}
+
+impl IndexerMetrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("bucky_bank_indexer".to_string()), None)
+            .expect("metric prefix is valid");
+
+        let events_processed = IntCounterVec::new(
+            Opts::new("events_processed_total", "Events written to the database, by event type"),
+            &["event_type"],
+        )
+        .expect("metric definition is valid");
+        let events_failed = IntCounterVec::new(
+            Opts::new(
+                "events_failed_total",
+                "Failed event processing attempts (including retries), by event type",
+            ),
+            &["event_type"],
+        )
+        .expect("metric definition is valid");
+        let event_process_seconds = HistogramVec::new(
+            HistogramOpts::new("event_process_seconds", "Latency of decoding and saving one event")
+                .buckets(EVENT_LATENCY_BUCKETS.to_vec()),
+            &["event_type"],
+        )
+        .expect("metric definition is valid");
+        let rpc_request_seconds = HistogramVec::new(
+            HistogramOpts::new("rpc_request_seconds", "Latency of Sui RPC calls, by method")
+                .buckets(RPC_LATENCY_BUCKETS.to_vec()),
+            &["method"],
+        )
+        .expect("metric definition is valid");
+        let rpc_errors = IntCounterVec::new(
+            Opts::new("rpc_errors_total", "Failed Sui RPC calls, by method"),
+            &["method"],
+        )
+        .expect("metric definition is valid");
+        let latest_event_timestamp_ms = IntGauge::new(
+            "latest_event_timestamp_ms",
+            "Chain timestamp of the newest event processed by this process",
+        )
+        .expect("metric definition is valid");
+        let indexing_lag_seconds = Gauge::new(
+            "indexing_lag_seconds",
+            "Wall clock minus the chain timestamp of the newest processed event",
+        )
+        .expect("metric definition is valid");
+        let last_caught_up_timestamp_seconds = IntGauge::new(
+            "last_caught_up_timestamp_seconds",
+            "Unix time when polling last reached the end of the event stream",
+        )
+        .expect("metric definition is valid");
+        let db_pool_connections =
+            IntGauge::new("db_pool_connections", "Open database connections")
+                .expect("metric definition is valid");
+        let db_pool_idle_connections =
+            IntGauge::new("db_pool_idle_connections", "Idle database connections")
+                .expect("metric definition is valid");
+        let db_pool_max_connections =
+            IntGauge::new("db_pool_max_connections", "Configured maximum database connections")
+                .expect("metric definition is valid");
+
+        for collector in [
+            Box::new(events_processed.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(events_failed.clone()),
+            Box::new(event_process_seconds.clone()),
+            Box::new(rpc_request_seconds.clone()),
+            Box::new(rpc_errors.clone()),
+            Box::new(latest_event_timestamp_ms.clone()),
+            Box::new(indexing_lag_seconds.clone()),
+            Box::new(last_caught_up_timestamp_seconds.clone()),
+            Box::new(db_pool_connections.clone()),
+            Box::new(db_pool_idle_connections.clone()),
+            Box::new(db_pool_max_connections.clone()),
+        ] {
+            registry.register(collector).expect("metric names are unique");
+        }
+
+        Self {
+            registry,
+            events_processed,
+            events_failed,
+            event_process_seconds,
+            rpc_request_seconds,
+            rpc_errors,
+            latest_event_timestamp_ms,
+            indexing_lag_seconds,
+            last_caught_up_timestamp_seconds,
+            db_pool_connections,
+            db_pool_idle_connections,
+            db_pool_max_connections,
+        }
+    }
+
+    /// 记录一次事件处理的结果和耗时，成功时同时更新最新事件时间
+    pub fn observe_event(
+        &self,
+        event_type: &str,
+        started: Instant,
+        timestamp_ms: Option<u64>,
+        succeeded: bool,
+    ) {
+        self.event_process_seconds
+            .with_label_values(&[event_type])
+            .observe(started.elapsed().as_secs_f64());
+
+        if !succeeded {
+            self.events_failed.with_label_values(&[event_type]).inc();
+            return;
+        }
+
+        self.events_processed.with_label_values(&[event_type]).inc();
+        // 重放的旧事件不会让最新时间倒退
+        if let Some(timestamp_ms) = timestamp_ms.map(|ts| ts as i64)
+            && timestamp_ms > self.latest_event_timestamp_ms.get()
+        {
+            self.latest_event_timestamp_ms.set(timestamp_ms);
+        }
+    }
+
+    /// 记录一次 RPC 调用的耗时，失败时计入错误数
+    pub fn observe_rpc<T, E>(&self, method: &str, started: Instant, result: &Result<T, E>) {
+        self.rpc_request_seconds
+            .with_label_values(&[method])
+            .observe(started.elapsed().as_secs_f64());
+        if result.is_err() {
+            self.rpc_errors.with_label_values(&[method]).inc();
+        }
+    }
+
+    /// 轮询追上链上最新事件时调用
+    pub fn mark_caught_up(&self) {
+        self.last_caught_up_timestamp_seconds
+            .set(chrono::Utc::now().timestamp());
+    }
+
+    /// 刷新索引延迟和连接池指标后按 Prometheus 文本格式输出
+    pub fn render(&self, pool: &PgPool) -> Result<String> {
+        let latest_ms = self.latest_event_timestamp_ms.get();
+        if latest_ms > 0 {
+            let lag_ms = (chrono::Utc::now().timestamp_millis() - latest_ms).max(0);
+            self.indexing_lag_seconds.set(lag_ms as f64 / 1000.0);
+        }
+
+        self.db_pool_connections.set(pool.size() as i64);
+        self.db_pool_idle_connections.set(pool.num_idle() as i64);
+        self.db_pool_max_connections
+            .set(pool.options().get_max_connections() as i64);
+
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8(buffer)?)
+    }
+}
@@ -0,0 +1,68 @@
+use tokio::signal;
+use tokio::sync::watch;
+use tracing::info;
+
+/// 进程级关闭信号：只安装一次 Ctrl+C / SIGTERM 监听，再通知所有持有副本的任务
+#[derive(Clone)]
+pub struct Shutdown {
+    receiver: watch::Receiver<bool>,
+}
+
+impl Shutdown {
+    /// 开始监听系统信号
+    pub fn listen() -> Self {
+        let (sender, receiver) = watch::channel(false);
+        tokio::spawn(async move {
+            wait_for_signal().await;
+            let _ = sender.send(true);
+        });
+        Self { receiver }
+    }
+
+    pub fn is_requested(&self) -> bool {
+        *self.receiver.borrow()
+    }
+
+    /// 等待关闭信号，已经收到信号时立即返回
+    pub async fn requested(&self) {
+        let mut receiver = self.receiver.clone();
+        // 发送端只会在发出信号后才被丢弃，出错同样视为已关闭
+        let _ = receiver.wait_for(|requested| *requested).await;
+    }
+
+    /// 可被关闭信号打断的等待，返回 false 表示等待期间收到了关闭信号
+    pub async fn sleep(&self, duration: std::time::Duration) -> bool {
+        tokio::select! {
+            _ = tokio::time::sleep(duration) => true,
+            _ = self.requested() => false,
+        }
+    }
+}
+
+async fn wait_for_signal() {
+    let ctrl_c = async {
+        signal::ctrl_c()
+            .await
+            .expect("Failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        signal::unix::signal(signal::unix::SignalKind::terminate())
+            .expect("Failed to install signal handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {
+            info!("Received Ctrl+C, shutting down");
+        }
+        _ = terminate => {
+            info!("Received terminate signal, shutting down");
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use super::{EventSink, SinkEvent};
+use crate::database::models::NewCursor;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// 把同一事件写入多个输出端
+///
+/// 主输出端的返回值决定事件是否为新写入（进而决定是否发布通知）。
+/// 主输出端先写入并提交游标，成功后再写入其他输出端，其他输出端不会收到主输出端未保存的事件。
+/// 其他输出端失败时返回错误，由索引器按原有策略重试，主输出端按事件ID跳过已写入的事件，
+/// 已成功的输出端可能收到重复事件；主输出端提交后进程退出时，其他输出端可能缺少该事件。
+pub struct FanoutSink {
+    primary: Arc<dyn EventSink>,
+    secondaries: Vec<Arc<dyn EventSink>>,
+}
+
+impl FanoutSink {
+    pub fn new(primary: Arc<dyn EventSink>, secondaries: Vec<Arc<dyn EventSink>>) -> Self {
+        Self {
+            primary,
+            secondaries,
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for FanoutSink {
+    fn name(&self) -> &'static str {
+        "fanout"
+    }
+
+    async fn write(&self, event: &SinkEvent, cursor: Option<&NewCursor>) -> Result<bool> {
+        let written = self.primary.write(event, cursor).await?;
+        for sink in &self.secondaries {
+            sink.write(event, cursor)
+                .await
+                .map_err(|e| anyhow!("{} sink: {}", sink.name(), e))?;
+        }
+        Ok(written)
+    }
+
+    async fn write_batch(&self, events: &[SinkEvent]) -> Result<u64> {
+        let written = self.primary.write_batch(events).await?;
+        for sink in &self.secondaries {
+            sink.write_batch(events)
+                .await
+                .map_err(|e| anyhow!("{} sink: {}", sink.name(), e))?;
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sink::MemorySink;
+    use crate::sink::memory::deposit_event;
+
+    struct FailingSink;
+
+    #[async_trait]
+    impl EventSink for FailingSink {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        async fn write(&self, _event: &SinkEvent, _cursor: Option<&NewCursor>) -> Result<bool> {
+            Err(anyhow!("unavailable"))
+        }
+    }
+
+    #[tokio::test]
+    async fn writes_every_sink_and_reports_the_primary_result() {
+        let primary = Arc::new(MemorySink::new());
+        let secondary = Arc::new(MemorySink::new());
+        let sink = FanoutSink::new(primary.clone(), vec![secondary.clone()]);
+        let event = deposit_event("tx1", 0);
+
+        assert!(sink.write(&event, None).await.unwrap());
+        // 重试时主输出端已写入过，返回 false
+        assert!(!sink.write(&event, None).await.unwrap());
+
+        assert_eq!(primary.events().len(), 1);
+        assert_eq!(secondary.events().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn skips_secondaries_when_the_primary_fails() {
+        let secondary = Arc::new(MemorySink::new());
+        let sink = FanoutSink::new(Arc::new(FailingSink), vec![secondary.clone()]);
+
+        assert!(sink.write(&deposit_event("tx1", 0), None).await.is_err());
+        assert!(
+            sink.write_batch(&[deposit_event("tx2", 0), deposit_event("tx2", 1)])
+                .await
+                .is_err()
+        );
+        assert!(secondary.events().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reports_secondary_failure_after_the_primary_is_written() {
+        let primary = Arc::new(MemorySink::new());
+        let sink = FanoutSink::new(primary.clone(), vec![Arc::new(FailingSink)]);
+
+        let error = sink
+            .write(&deposit_event("tx1", 0), None)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().starts_with("failing sink"), "{}", error);
+
+        let error = sink
+            .write_batch(&[deposit_event("tx2", 0), deposit_event("tx2", 1)])
+            .await
+            .unwrap_err();
+        assert!(error.to_string().starts_with("failing sink"), "{}", error);
+
+        assert_eq!(primary.events().len(), 3);
+    }
+}
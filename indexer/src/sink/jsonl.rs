@@ -0,0 +1,60 @@
+use super::{EventSink, SinkEvent};
+use crate::database::models::NewCursor;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// 把事件按行追加写入 JSON Lines 文件，每行一个 `SinkEvent`
+///
+/// 文件不去重，重试或回填时同一事件可能出现多行，消费方可按 tx_digest + event_seq 去重。
+pub struct JsonlSink {
+    file: Mutex<File>,
+}
+
+impl JsonlSink {
+    pub async fn open(path: &str) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+            .with_context(|| format!("Failed to open JSONL sink file {}", path))?;
+
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+
+    async fn append(&self, events: &[SinkEvent]) -> Result<()> {
+        let mut buffer = Vec::new();
+        for event in events {
+            serde_json::to_writer(&mut buffer, event)?;
+            buffer.push(b'\n');
+        }
+
+        // 整批一次写入并落盘，避免并发写入时行被截断交错
+        let mut file = self.file.lock().await;
+        file.write_all(&buffer).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventSink for JsonlSink {
+    fn name(&self) -> &'static str {
+        "jsonl"
+    }
+
+    async fn write(&self, event: &SinkEvent, _cursor: Option<&NewCursor>) -> Result<bool> {
+        self.append(std::slice::from_ref(event)).await?;
+        Ok(true)
+    }
+
+    async fn write_batch(&self, events: &[SinkEvent]) -> Result<u64> {
+        self.append(events).await?;
+        Ok(events.len() as u64)
+    }
+}
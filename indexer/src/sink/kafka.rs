@@ -0,0 +1,254 @@
+use super::{EventSink, SinkEvent};
+use crate::database::models::NewCursor;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const KAFKA_JSON_CONTENT_TYPE: &str = "application/vnd.kafka.json.v2+json";
+const KAFKA_ACCEPT: &str = "application/vnd.kafka.v2+json";
+
+/// 通过 Kafka REST Proxy v2 接口（Confluent REST Proxy、Redpanda HTTP Proxy 等）发布事件
+///
+/// 消息 key 为存钱罐 ID，同一存钱罐的事件（包括提取请求的审批）落在同一分区并保持顺序。
+/// 发布是至少一次语义，消费方需按 tx_digest + event_seq 去重。
+pub struct KafkaRestSink {
+    client: reqwest::Client,
+    endpoint: String,
+}
+
+#[derive(Serialize)]
+struct ProduceRequest<'a> {
+    records: Vec<ProduceRecord<'a>>,
+}
+
+#[derive(Serialize)]
+struct ProduceRecord<'a> {
+    key: &'a str,
+    value: &'a SinkEvent,
+}
+
+#[derive(Deserialize)]
+struct ProduceResponse {
+    #[serde(default)]
+    offsets: Vec<ProduceOffset>,
+}
+
+#[derive(Deserialize)]
+struct ProduceOffset {
+    error_code: Option<i64>,
+    error: Option<String>,
+}
+
+impl KafkaRestSink {
+    pub fn new(rest_url: &str, topic: &str, timeout_seconds: u64) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(timeout_seconds.max(1)))
+            .build()?;
+
+        Ok(Self {
+            client,
+            endpoint: format!("{}/topics/{}", rest_url.trim_end_matches('/'), topic),
+        })
+    }
+
+    fn record_key(event: &SinkEvent) -> &str {
+        event.event.subject().0
+    }
+
+    async fn produce(&self, events: &[SinkEvent]) -> Result<()> {
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let request = ProduceRequest {
+            records: events
+                .iter()
+                .map(|event| ProduceRecord {
+                    key: Self::record_key(event),
+                    value: event,
+                })
+                .collect(),
+        };
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header(CONTENT_TYPE, KAFKA_JSON_CONTENT_TYPE)
+            .header(ACCEPT, KAFKA_ACCEPT)
+            .json(&request)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("Kafka REST proxy returned {}: {}", status, body));
+        }
+
+        // 整体成功时单条记录仍可能写入失败，需要逐条检查
+        let response: ProduceResponse = response.json().await?;
+        if let Some(failed) = response
+            .offsets
+            .iter()
+            .find(|offset| offset.error_code.is_some())
+        {
+            return Err(anyhow!(
+                "Kafka REST proxy rejected record: {} (code {})",
+                failed.error.as_deref().unwrap_or("unknown error"),
+                failed.error_code.unwrap_or_default()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventSink for KafkaRestSink {
+    fn name(&self) -> &'static str {
+        "kafka"
+    }
+
+    async fn write(&self, event: &SinkEvent, _cursor: Option<&NewCursor>) -> Result<bool> {
+        self.produce(std::slice::from_ref(event)).await?;
+        Ok(true)
+    }
+
+    async fn write_batch(&self, events: &[SinkEvent]) -> Result<u64> {
+        self.produce(events).await?;
+        Ok(events.len() as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::models::WithdrawalStatus;
+    use crate::indexer::{BuckyBankEvent, WithdrawalAudit};
+    use crate::sink::memory::deposit_event;
+    use axum::Router;
+    use axum::extract::Path;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    const BUCKY_BANK_ID: &str =
+        "0xd7dd42e481af321a09533e727dd53c0ee58ef2257333e3471358254363c3c3de";
+
+    // 本地 REST Proxy：记录请求的 topic、Content-Type 和请求体，返回固定的响应
+    async fn spawn_proxy(
+        status: StatusCode,
+        response: serde_json::Value,
+    ) -> (
+        String,
+        mpsc::UnboundedReceiver<(String, String, serde_json::Value)>,
+    ) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let app = Router::new().route(
+            "/topics/:topic",
+            post(
+                move |Path(topic): Path<String>, headers: HeaderMap, body: String| {
+                    let sender = sender.clone();
+                    let response = response.clone();
+                    async move {
+                        let content_type = headers[CONTENT_TYPE].to_str().unwrap().to_string();
+                        let _ = sender.send((
+                            topic,
+                            content_type,
+                            serde_json::from_str(&body).unwrap(),
+                        ));
+                        (status, axum::Json(response))
+                    }
+                },
+            ),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}/", address), receiver)
+    }
+
+    fn audit_event() -> SinkEvent {
+        SinkEvent {
+            event_type: "EventWithdrawalApproved",
+            event: BuckyBankEvent::WithdrawalAudited(WithdrawalAudit {
+                request_id: "0x8048af51a7c16e4a0bd8555c8147e5e2e7a0b6ca59499d8ede96aea24db44b8d"
+                    .to_string(),
+                bucky_bank_id: BUCKY_BANK_ID.to_string(),
+                amount: 3000000,
+                requester: "0x27b2306354b0537a9ac9eddb26e10f327d6ef660333902e313b1bcb4353c5d3f"
+                    .to_string(),
+                reason: "I need it".to_string(),
+                status: WithdrawalStatus::Approved,
+                audited_by: "0x27b2306354b0537a9ac9eddb26e10f327d6ef660333902e313b1bcb4353c5d3f"
+                    .to_string(),
+                audit_at_ms: 1760166241636,
+            }),
+            ..deposit_event("tx1", 1)
+        }
+    }
+
+    #[tokio::test]
+    async fn publishes_records_keyed_by_bucky_bank() {
+        let (url, mut receiver) = spawn_proxy(
+            StatusCode::OK,
+            serde_json::json!({"offsets": [{"partition": 0, "offset": 1}, {"partition": 0, "offset": 2}]}),
+        )
+        .await;
+        let sink = KafkaRestSink::new(&url, "bucky-bank-events", 5).unwrap();
+
+        let written = sink
+            .write_batch(&[deposit_event("tx1", 0), audit_event()])
+            .await
+            .unwrap();
+        assert_eq!(written, 2);
+
+        let (topic, content_type, body) = receiver.recv().await.unwrap();
+        assert_eq!(topic, "bucky-bank-events");
+        assert_eq!(content_type, KAFKA_JSON_CONTENT_TYPE);
+
+        let records = body["records"].as_array().unwrap();
+        assert_eq!(records.len(), 2);
+        // 审批事件与存款事件使用同一个 key，落在同一分区
+        for record in records {
+            assert_eq!(record["key"], BUCKY_BANK_ID);
+        }
+        assert_eq!(records[0]["value"]["event_type"], "DepositMade");
+        assert_eq!(records[0]["value"]["data"]["amount"], 3000000);
+        assert_eq!(records[1]["value"]["data"]["status"], "Approved");
+    }
+
+    #[tokio::test]
+    async fn fails_when_a_record_is_rejected() {
+        let (url, _receiver) = spawn_proxy(
+            StatusCode::OK,
+            serde_json::json!({"offsets": [{"error_code": 40403, "error": "topic not found"}]}),
+        )
+        .await;
+        let sink = KafkaRestSink::new(&url, "bucky-bank-events", 5).unwrap();
+
+        let error = sink
+            .write(&deposit_event("tx1", 0), None)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("topic not found"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn fails_on_error_status() {
+        let (url, _receiver) = spawn_proxy(
+            StatusCode::SERVICE_UNAVAILABLE,
+            serde_json::json!({"error_code": 50301, "message": "unavailable"}),
+        )
+        .await;
+        let sink = KafkaRestSink::new(&url, "bucky-bank-events", 5).unwrap();
+
+        let error = sink
+            .write(&deposit_event("tx1", 0), None)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("503"), "{}", error);
+    }
+}
@@ -0,0 +1,73 @@
+use super::{EventSink, SinkEvent};
+use crate::database::models::{NewCursor, NewDepositMadeEvent};
+use crate::indexer::BuckyBankEvent;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+/// 把事件保存在内存中，按 tx_digest + event_seq 去重，供输出端的测试使用
+#[derive(Default)]
+pub struct MemorySink {
+    events: Mutex<Vec<SinkEvent>>,
+    seen: Mutex<HashSet<String>>,
+}
+
+impl MemorySink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 按写入顺序返回已保存的事件
+    pub fn events(&self) -> Vec<SinkEvent> {
+        self.events
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+#[async_trait]
+impl EventSink for MemorySink {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn write(&self, event: &SinkEvent, _cursor: Option<&NewCursor>) -> Result<bool> {
+        if !self
+            .seen
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(event.event_id())
+        {
+            return Ok(false);
+        }
+
+        self.events
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(event.clone());
+        Ok(true)
+    }
+}
+
+/// 测试用的存款事件
+pub fn deposit_event(tx_digest: &str, event_seq: u64) -> SinkEvent {
+    SinkEvent {
+        event_type: "DepositMade",
+        tx_digest: tx_digest.to_string(),
+        event_seq,
+        timestamp_ms: Some(1760166241636),
+        event: BuckyBankEvent::DepositMade(NewDepositMadeEvent {
+            bucky_bank_id: "0xd7dd42e481af321a09533e727dd53c0ee58ef2257333e3471358254363c3c3de"
+                .to_string(),
+            amount: 3000000,
+            depositor: "0x27b2306354b0537a9ac9eddb26e10f327d6ef660333902e313b1bcb4353c5d3f"
+                .to_string(),
+            created_at_ms: 1760166241636,
+            tx_digest: tx_digest.to_string(),
+            event_seq: event_seq as i64,
+        }),
+        notification: None,
+    }
+}
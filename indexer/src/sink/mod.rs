@@ -0,0 +1,115 @@
+mod fanout;
+mod jsonl;
+mod kafka;
+#[cfg(test)]
+mod memory;
+mod postgres;
+
+pub use fanout::FanoutSink;
+pub use jsonl::JsonlSink;
+pub use kafka::KafkaRestSink;
+#[cfg(test)]
+pub use memory::MemorySink;
+pub use postgres::PostgresSink;
+
+use crate::config::SinkConfig;
+use crate::database::{Database, models::NewCursor};
+use crate::indexer::{BuckyBankEvent, EventType, IndexedEvent};
+use anyhow::Result;
+use async_trait::async_trait;
+use bucky_bank_api::EventNotification;
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::info;
+
+/// 交给输出端的事件：链上位置和解析后的事件数据
+#[derive(Debug, Clone, Serialize)]
+pub struct SinkEvent {
+    /// 链上事件结构体名称，如 DepositMade、EventWithdrawalApproved
+    pub event_type: &'static str,
+    pub tx_digest: String,
+    pub event_seq: u64,
+    pub timestamp_ms: Option<u64>,
+    #[serde(rename = "data")]
+    pub event: BuckyBankEvent,
+    /// 实时推送和 webhook 使用的通知，Postgres 输出端在事件写入的事务内据此生成 webhook 投递记录
+    #[serde(skip)]
+    pub notification: Option<EventNotification>,
+}
+
+impl SinkEvent {
+    pub fn new(event: &IndexedEvent, event_type: &EventType, decoded: BuckyBankEvent) -> Self {
+        Self {
+            event_type: event_type.name(),
+            tx_digest: event.id.tx_digest.to_string(),
+            event_seq: event.id.event_seq,
+            timestamp_ms: event.timestamp_ms,
+            event: decoded,
+            notification: None,
+        }
+    }
+
+    pub fn with_notification(mut self, notification: EventNotification) -> Self {
+        self.notification = Some(notification);
+        self
+    }
+
+    /// `tx_digest:event_seq`，可作为事件的唯一标识
+    pub fn event_id(&self) -> String {
+        format!("{}:{}", self.tx_digest, self.event_seq)
+    }
+}
+
+/// 事件输出端，索引器把解析后的事件交给它写入
+///
+/// 同一事件可能因重试、重放或回填被多次写入，实现需要能容忍重复。
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    /// 输出端名称，用于日志
+    fn name(&self) -> &'static str;
+
+    /// 写入单个事件，返回是否为新写入（已写入过的事件返回 false）
+    ///
+    /// cursor 为写入该事件后的轮询游标，支持事务的输出端应与事件一起提交，其他输出端可以忽略。
+    async fn write(&self, event: &SinkEvent, cursor: Option<&NewCursor>) -> Result<bool>;
+
+    /// 回填时批量写入连续的同类型事件，返回新写入的数量
+    ///
+    /// 返回错误时索引器会改为逐条写入，默认实现即逐条写入。
+    async fn write_batch(&self, events: &[SinkEvent]) -> Result<u64> {
+        let mut written = 0;
+        for event in events {
+            if self.write(event, None).await? {
+                written += 1;
+            }
+        }
+        Ok(written)
+    }
+}
+
+/// 按配置创建输出端：Postgres 始终是主输出端，配置了 JSONL 文件或 Kafka 时一起写入
+pub async fn build_sink(config: &SinkConfig, db: Arc<Database>) -> Result<Arc<dyn EventSink>> {
+    let primary: Arc<dyn EventSink> = Arc::new(PostgresSink::new(db));
+    let mut secondaries: Vec<Arc<dyn EventSink>> = Vec::new();
+
+    if let Some(path) = &config.jsonl_path {
+        info!("Writing indexed events to JSONL file {}", path);
+        secondaries.push(Arc::new(JsonlSink::open(path).await?));
+    }
+    if let Some(url) = &config.kafka_rest_url {
+        info!(
+            "Publishing indexed events to Kafka topic {} via {}",
+            config.kafka_topic, url
+        );
+        secondaries.push(Arc::new(KafkaRestSink::new(
+            url,
+            &config.kafka_topic,
+            config.kafka_timeout_seconds,
+        )?));
+    }
+
+    if secondaries.is_empty() {
+        return Ok(primary);
+    }
+    Ok(Arc::new(FanoutSink::new(primary, secondaries)))
+}
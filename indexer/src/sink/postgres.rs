@@ -0,0 +1,159 @@
+use super::{EventSink, SinkEvent};
+use crate::database::{Database, models::NewCursor};
+use crate::indexer::BuckyBankEvent;
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+use tracing::{error, info};
+
+/// 默认输出端：写入 Postgres 索引表，游标与事件在同一事务中提交
+pub struct PostgresSink {
+    db: Arc<Database>,
+}
+
+impl PostgresSink {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+}
+
+// 从一批事件中取出指定变体的数据
+macro_rules! collect_variant {
+    ($events:expr, $variant:ident) => {
+        $events
+            .iter()
+            .filter_map(|event| match &event.event {
+                BuckyBankEvent::$variant(data) => Some(data.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+}
+
+#[async_trait]
+impl EventSink for PostgresSink {
+    fn name(&self) -> &'static str {
+        "postgres"
+    }
+
+    async fn write(&self, event: &SinkEvent, cursor: Option<&NewCursor>) -> Result<bool> {
+        let notification = event.notification.as_ref();
+        // 保存方法返回 None 或 false 表示事件已写入过
+        let written = match &event.event {
+            BuckyBankEvent::BuckyBankCreated(data) => self
+                .db
+                .save_bucky_bank_created_event(data, cursor)
+                .await
+                .map(|saved| saved.is_some()),
+            BuckyBankEvent::DepositMade(data) => self
+                .db
+                .save_deposit_made_event(data, cursor, notification)
+                .await
+                .map(|saved| saved.is_some()),
+            BuckyBankEvent::WithdrawalRequested(data) => self
+                .db
+                .save_withdrawal_request_event(data, cursor, notification)
+                .await
+                .map(|saved| saved.is_some()),
+            BuckyBankEvent::WithdrawalAudited(audit) => {
+                self.db
+                    .apply_withdrawal_audit_event(
+                        &audit.request_id,
+                        &audit.status,
+                        &audit.audited_by,
+                        audit.audit_at_ms,
+                        cursor,
+                        notification,
+                    )
+                    .await
+            }
+            BuckyBankEvent::EventWithdrawed(data) => self
+                .db
+                .save_event_withdrawed_event(data, cursor, notification)
+                .await
+                .map(|saved| saved.is_some()),
+            BuckyBankEvent::BankReward(data) => self
+                .db
+                .save_bank_reward_event(data, cursor)
+                .await
+                .map(|saved| saved.is_some()),
+            BuckyBankEvent::ClaimReward(data) => self
+                .db
+                .save_reward_claim_event(data, cursor)
+                .await
+                .map(|saved| saved.is_some()),
+        };
+
+        match &written {
+            Ok(true) => info!("Saved {} event {}", event.event_type, event.event_id()),
+            Ok(false) => info!(
+                "{} event {} already indexed, skipping",
+                event.event_type,
+                event.event_id()
+            ),
+            Err(e) => error!(
+                "Failed to save {} event {}: {}",
+                event.event_type,
+                event.event_id(),
+                e
+            ),
+        }
+        written
+    }
+
+    /// 一批同类型事件在一个事务中写入
+    async fn write_batch(&self, events: &[SinkEvent]) -> Result<u64> {
+        let Some(first) = events.first() else {
+            return Ok(0);
+        };
+
+        match &first.event {
+            BuckyBankEvent::BuckyBankCreated(_) => {
+                self.db
+                    .save_bucky_bank_created_events_batch(&collect_variant!(
+                        events,
+                        BuckyBankCreated
+                    ))
+                    .await
+            }
+            BuckyBankEvent::DepositMade(_) => {
+                self.db
+                    .save_deposit_made_events_batch(&collect_variant!(events, DepositMade))
+                    .await
+            }
+            BuckyBankEvent::WithdrawalRequested(_) => {
+                self.db
+                    .save_withdrawal_request_events_batch(&collect_variant!(
+                        events,
+                        WithdrawalRequested
+                    ))
+                    .await
+            }
+            BuckyBankEvent::EventWithdrawed(_) => {
+                self.db
+                    .save_withdrawed_events_batch(&collect_variant!(events, EventWithdrawed))
+                    .await
+            }
+            BuckyBankEvent::BankReward(_) => {
+                self.db
+                    .save_bank_reward_events_batch(&collect_variant!(events, BankReward))
+                    .await
+            }
+            BuckyBankEvent::ClaimReward(_) => {
+                self.db
+                    .save_reward_claim_events_batch(&collect_variant!(events, ClaimReward))
+                    .await
+            }
+            // 审批事件只更新已有的提取请求，没有批量写入方法，逐条处理
+            BuckyBankEvent::WithdrawalAudited(_) => {
+                let mut written = 0;
+                for event in events {
+                    if self.write(event, None).await? {
+                        written += 1;
+                    }
+                }
+                Ok(written)
+            }
+        }
+    }
+}
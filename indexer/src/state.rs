@@ -0,0 +1,152 @@
+use crate::config::IndexingConfig;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+/// 轮询任务的运行状态，由 `run_continuous_polling` 更新，`/health` 和 `/ready` 读取
+#[derive(Debug)]
+pub struct IndexerState {
+    started_at: DateTime<Utc>,
+    max_lag_seconds: i64,
+    max_poll_age_seconds: i64,
+    progress: RwLock<PollProgress>,
+}
+
+#[derive(Debug, Default)]
+struct PollProgress {
+    running: bool,
+    last_poll_at: Option<DateTime<Utc>>,
+    caught_up: bool,
+    latest_event_timestamp_ms: Option<i64>,
+    last_error: Option<String>,
+    last_error_at: Option<DateTime<Utc>>,
+}
+
+/// 某一时刻的轮询状态快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexerStatus {
+    pub running: bool,
+    /// 最近一次成功轮询的时间
+    pub last_poll_at: Option<DateTime<Utc>>,
+    /// 距离最近一次成功轮询的秒数，还没有成功轮询时从启动时间算起
+    pub seconds_since_last_poll: i64,
+    /// 最近一次轮询是否已追上链上最新事件
+    pub caught_up: bool,
+    pub latest_event_timestamp_ms: Option<i64>,
+    /// 索引延迟(秒)：已追上时为 0，否则为当前时间与最新已处理事件的差值
+    pub lag_seconds: Option<i64>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
+}
+
+impl IndexerState {
+    pub fn new(indexing: &IndexingConfig) -> Self {
+        Self {
+            started_at: Utc::now(),
+            max_lag_seconds: indexing.ready_max_lag_seconds as i64,
+            max_poll_age_seconds: indexing.ready_max_poll_age_seconds as i64,
+            progress: RwLock::new(PollProgress::default()),
+        }
+    }
+
+    fn update(&self, f: impl FnOnce(&mut PollProgress)) {
+        // 写锁内只做字段赋值，不会在持锁时 panic，锁中毒时沿用其中的数据
+        let mut progress = self.progress.write().unwrap_or_else(|e| e.into_inner());
+        f(&mut progress);
+    }
+
+    pub fn mark_started(&self) {
+        self.update(|progress| progress.running = true);
+    }
+
+    /// 轮询循环退出，error 为空表示正常关闭
+    pub fn mark_stopped(&self, error: Option<&str>) {
+        self.update(|progress| {
+            progress.running = false;
+            if let Some(error) = error {
+                progress.last_error = Some(error.to_string());
+                progress.last_error_at = Some(Utc::now());
+            }
+        });
+    }
+
+    /// 一次轮询成功完成，has_next_page 为 false 表示已追上链上最新事件
+    pub fn record_poll(&self, has_next_page: bool) {
+        self.update(|progress| {
+            progress.last_poll_at = Some(Utc::now());
+            progress.caught_up = !has_next_page;
+        });
+    }
+
+    pub fn record_error(&self, error: &str) {
+        self.update(|progress| {
+            progress.last_error = Some(error.to_string());
+            progress.last_error_at = Some(Utc::now());
+        });
+    }
+
+    /// 事件写入成功后记录其链上时间，重放的旧事件不会让最新时间倒退
+    pub fn record_event_timestamp(&self, timestamp_ms: Option<u64>) {
+        let Some(timestamp_ms) = timestamp_ms.map(|ts| ts as i64) else {
+            return;
+        };
+        self.update(|progress| {
+            if progress
+                .latest_event_timestamp_ms
+                .is_none_or(|latest| timestamp_ms > latest)
+            {
+                progress.latest_event_timestamp_ms = Some(timestamp_ms);
+            }
+        });
+    }
+
+    pub fn snapshot(&self) -> IndexerStatus {
+        let progress = self.progress.read().unwrap_or_else(|e| e.into_inner());
+        let now = Utc::now();
+
+        let lag_seconds = if progress.caught_up {
+            Some(0)
+        } else {
+            progress
+                .latest_event_timestamp_ms
+                .map(|ts| ((now.timestamp_millis() - ts) / 1000).max(0))
+        };
+
+        IndexerStatus {
+            running: progress.running,
+            last_poll_at: progress.last_poll_at,
+            seconds_since_last_poll: (now - progress.last_poll_at.unwrap_or(self.started_at))
+                .num_seconds(),
+            caught_up: progress.caught_up,
+            latest_event_timestamp_ms: progress.latest_event_timestamp_ms,
+            lag_seconds,
+            last_error: progress.last_error.clone(),
+            last_error_at: progress.last_error_at,
+        }
+    }
+
+    /// 不满足就绪条件的原因，为空表示就绪
+    pub fn readiness_problems(&self, status: &IndexerStatus) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if !status.running {
+            problems.push("indexer polling is not running".to_string());
+        }
+        if status.seconds_since_last_poll > self.max_poll_age_seconds {
+            problems.push(format!(
+                "no successful poll for {}s (limit {}s)",
+                status.seconds_since_last_poll, self.max_poll_age_seconds
+            ));
+        }
+        if let Some(lag) = status.lag_seconds
+            && lag > self.max_lag_seconds
+        {
+            problems.push(format!(
+                "indexing lag {}s exceeds {}s",
+                lag, self.max_lag_seconds
+            ));
+        }
+
+        problems
+    }
+}
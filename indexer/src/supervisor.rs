@@ -0,0 +1,85 @@
+use crate::config::IndexingConfig;
+use crate::indexer::{BuckyBankIndexer, EventProcessingHalted};
+use crate::shutdown::Shutdown;
+use anyhow::{Result, anyhow};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{Instrument, error, info, info_span, warn};
+
+/// 监督轮询任务：轮询因错误或 panic 退出时按指数退避重启，不影响同进程内的 HTTP 服务
+///
+/// 收到关闭信号时等当前一页处理完后退出；事件处理按 halt 策略停止时不再重启，等待人工处理。
+pub struct IndexerSupervisor {
+    indexer: Arc<BuckyBankIndexer>,
+    shutdown: Shutdown,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl IndexerSupervisor {
+    pub fn new(indexer: Arc<BuckyBankIndexer>, shutdown: Shutdown, indexing: &IndexingConfig) -> Self {
+        let initial_backoff = Duration::from_secs(indexing.restart_initial_backoff_seconds.max(1));
+        Self {
+            indexer,
+            shutdown,
+            initial_backoff,
+            max_backoff: Duration::from_secs(indexing.restart_max_backoff_seconds)
+                .max(initial_backoff),
+        }
+    }
+
+    pub async fn run(&self) -> Result<()> {
+        let mut restarts: u32 = 0;
+        let mut backoff = self.initial_backoff;
+
+        loop {
+            let started = Instant::now();
+
+            // 在单独的任务中轮询，panic 时也能被监督者捕获
+            let indexer = self.indexer.clone();
+            let shutdown = self.shutdown.clone();
+            let handle = tokio::spawn(
+                async move { indexer.run_continuous_polling(&shutdown).await }
+                    .instrument(info_span!("continuous_polling", restarts)),
+            );
+
+            let error = match handle.await {
+                Ok(Ok(())) => {
+                    info!("Polling stopped gracefully");
+                    return Ok(());
+                }
+                Ok(Err(e)) if e.is::<EventProcessingHalted>() => {
+                    error!("Indexer halted, not restarting: {}", e);
+                    return Err(e);
+                }
+                Ok(Err(e)) => e,
+                Err(join_error) => {
+                    let e = anyhow!("Polling task panicked: {}", join_error);
+                    self.indexer.state().mark_stopped(Some(&e.to_string()));
+                    e
+                }
+            };
+
+            if self.shutdown.is_requested() {
+                info!("Polling stopped during shutdown: {}", error);
+                return Ok(());
+            }
+
+            // 稳定运行超过最大退避时间后再失败，视为新的故障，从初始退避重新计算
+            if started.elapsed() > self.max_backoff {
+                backoff = self.initial_backoff;
+            }
+
+            restarts += 1;
+            warn!(
+                "Polling stopped unexpectedly, restarting in {:?} (restart #{}): {}",
+                backoff, restarts, error
+            );
+            if !self.shutdown.sleep(backoff).await {
+                info!("Received shutdown signal while waiting to restart polling");
+                return Ok(());
+            }
+            backoff = (backoff * 2).min(self.max_backoff);
+        }
+    }
+}
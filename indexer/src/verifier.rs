@@ -0,0 +1,426 @@
+use crate::database::{Database, models::IndexedBuckyBankState};
+use crate::metrics::METRICS;
+use crate::shutdown::Shutdown;
+use anyhow::Result;
+use serde::Serialize;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use sui_sdk::SuiClient;
+use sui_sdk::rpc_types::{SuiObjectDataOptions, SuiParsedData};
+use sui_sdk::types::base_types::ObjectID;
+use tracing::{debug, error, info, warn};
+
+/// 链上 BuckyBank 对象中参与核对的字段
+#[derive(Debug, Clone, Serialize)]
+pub struct OnChainBuckyBank {
+    pub current_balance: u64,
+    pub status: String,
+    pub deposit_count: u64,
+    pub last_deposit_ms: u64,
+}
+
+impl OnChainBuckyBank {
+    /// 从对象内容的 JSON 表示解析，u64 字段在 JSON 中为字符串，枚举为 {"variant": ..., "fields": {}}
+    pub fn from_json(fields: &serde_json::Value) -> Result<Self> {
+        let u64_field = |name: &str| -> Result<u64> {
+            fields
+                .get(name)
+                .and_then(|v| v.as_str())
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| anyhow::anyhow!("Missing or invalid {} field", name))
+        };
+
+        let status = fields
+            .get("status")
+            .and_then(|status| status.get("variant"))
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing or invalid status field"))?;
+
+        Ok(Self {
+            current_balance: u64_field("current_balance")?,
+            status: status.to_string(),
+            deposit_count: u64_field("deposit_count")?,
+            last_deposit_ms: u64_field("last_deposit_ms")?,
+        })
+    }
+}
+
+/// 读取链上 BuckyBank 对象，核对逻辑只依赖该接口，可以用桩实现替换 RPC
+pub trait BuckyBankReader: Send + Sync {
+    fn get_bucky_bank(
+        &self,
+        bucky_bank_id: &str,
+    ) -> impl Future<Output = Result<Option<OnChainBuckyBank>>> + Send;
+}
+
+impl BuckyBankReader for SuiClient {
+    async fn get_bucky_bank(&self, bucky_bank_id: &str) -> Result<Option<OnChainBuckyBank>> {
+        let object_id: ObjectID = bucky_bank_id.parse()?;
+
+        let started = Instant::now();
+        let response = self
+            .read_api()
+            .get_object_with_options(object_id, SuiObjectDataOptions::new().with_content())
+            .await;
+        METRICS.observe_rpc("get_object", started, &response);
+        let response = response?;
+
+        let Some(data) = response.data else {
+            return Ok(None);
+        };
+
+        match data.content {
+            Some(SuiParsedData::MoveObject(object)) => {
+                OnChainBuckyBank::from_json(&object.fields.to_json_value()).map(Some)
+            }
+            _ => Err(anyhow::anyhow!(
+                "Object {} is not a BuckyBank Move object",
+                bucky_bank_id
+            )),
+        }
+    }
+}
+
+/// 读取索引数据中的 BuckyBank 状态，核对逻辑只依赖该接口，可以用桩实现替换数据库
+pub trait IndexedStateReader: Send + Sync {
+    fn get_bucky_bank_ids(&self) -> impl Future<Output = Result<Vec<String>>> + Send;
+
+    fn get_indexed_bucky_bank_state(
+        &self,
+        bucky_bank_id: &str,
+    ) -> impl Future<Output = Result<Option<IndexedBuckyBankState>>> + Send;
+}
+
+impl IndexedStateReader for Database {
+    async fn get_bucky_bank_ids(&self) -> Result<Vec<String>> {
+        Database::get_bucky_bank_ids(self).await
+    }
+
+    async fn get_indexed_bucky_bank_state(
+        &self,
+        bucky_bank_id: &str,
+    ) -> Result<Option<IndexedBuckyBankState>> {
+        Database::get_indexed_bucky_bank_state(self, bucky_bank_id).await
+    }
+}
+
+/// 单个字段的核对差异
+#[derive(Debug, Serialize)]
+pub struct FieldMismatch {
+    pub field: &'static str,
+    pub indexed: serde_json::Value,
+    pub on_chain: serde_json::Value,
+}
+
+/// 单个 BuckyBank 的核对结果
+#[derive(Debug, Serialize)]
+pub struct VerificationResult {
+    pub bucky_bank_id: String,
+    pub matched: bool,
+    pub indexed: Option<IndexedBuckyBankState>,
+    pub on_chain: Option<OnChainBuckyBank>,
+    pub mismatches: Vec<FieldMismatch>,
+}
+
+/// 核对指标，供 /api/verify 查询
+#[derive(Debug, Default)]
+struct VerifierMetrics {
+    runs: AtomicU64,
+    banks_checked: AtomicU64,
+    mismatches_total: AtomicU64,
+    last_run_mismatched: AtomicU64,
+    last_run_at_ms: AtomicU64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VerifierStats {
+    pub runs: u64,
+    pub banks_checked: u64,
+    pub mismatches_total: u64,
+    pub last_run_mismatched: u64,
+    pub last_run_at_ms: Option<u64>,
+}
+
+/// 定期读取链上 BuckyBank 对象，与索引数据核对 current_balance、status、deposit_count 和 last_deposit_ms
+pub struct StateVerifier<R: BuckyBankReader = SuiClient, S: IndexedStateReader = Database> {
+    reader: Arc<R>,
+    db: Arc<S>,
+    interval: Duration,
+    metrics: VerifierMetrics,
+}
+
+impl<R: BuckyBankReader, S: IndexedStateReader> StateVerifier<R, S> {
+    pub fn new(reader: Arc<R>, db: Arc<S>, interval_seconds: u64) -> Self {
+        Self {
+            reader,
+            db,
+            interval: Duration::from_secs(interval_seconds),
+            metrics: VerifierMetrics::default(),
+        }
+    }
+
+    /// 核对单个 BuckyBank
+    pub async fn verify(&self, bucky_bank_id: &str) -> Result<VerificationResult> {
+        let indexed = self.db.get_indexed_bucky_bank_state(bucky_bank_id).await?;
+        let on_chain = self.reader.get_bucky_bank(bucky_bank_id).await?;
+
+        let mismatches = match (&indexed, &on_chain) {
+            (Some(indexed), Some(on_chain)) => Self::compare(indexed, on_chain),
+            // 一侧缺失时整条记录视为不一致
+            (indexed, on_chain) => vec![FieldMismatch {
+                field: "object",
+                indexed: serde_json::json!(indexed.is_some()),
+                on_chain: serde_json::json!(on_chain.is_some()),
+            }],
+        };
+
+        let result = VerificationResult {
+            bucky_bank_id: bucky_bank_id.to_string(),
+            matched: mismatches.is_empty(),
+            indexed,
+            on_chain,
+            mismatches,
+        };
+
+        self.metrics.banks_checked.fetch_add(1, Ordering::Relaxed);
+        if !result.matched {
+            self.metrics.mismatches_total.fetch_add(1, Ordering::Relaxed);
+            warn!(
+                "BuckyBank {} differs from chain: {:?}",
+                bucky_bank_id, result.mismatches
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// 核对全部已索引的 BuckyBank，返回不一致的数量
+    pub async fn verify_all(&self) -> Result<u64> {
+        let bucky_bank_ids = self.db.get_bucky_bank_ids().await?;
+
+        let mut mismatched = 0;
+        for bucky_bank_id in &bucky_bank_ids {
+            match self.verify(bucky_bank_id).await {
+                Ok(result) if !result.matched => mismatched += 1,
+                Ok(_) => debug!("BuckyBank {} matches chain state", bucky_bank_id),
+                Err(e) => error!("Failed to verify BuckyBank {}: {}", bucky_bank_id, e),
+            }
+        }
+
+        self.metrics.runs.fetch_add(1, Ordering::Relaxed);
+        self.metrics
+            .last_run_mismatched
+            .store(mismatched, Ordering::Relaxed);
+        self.metrics
+            .last_run_at_ms
+            .store(chrono::Utc::now().timestamp_millis() as u64, Ordering::Relaxed);
+
+        info!(
+            "Verified {} BuckyBanks against chain state, {} mismatched",
+            bucky_bank_ids.len(),
+            mismatched
+        );
+        Ok(mismatched)
+    }
+
+    /// 按配置的间隔持续核对直到收到关闭信号，间隔为 0 时直接返回
+    pub async fn run_periodic(&self, shutdown: Shutdown) {
+        if self.interval.is_zero() {
+            info!("Chain state verification disabled");
+            return;
+        }
+
+        while !shutdown.is_requested() {
+            if let Err(e) = self.verify_all().await {
+                error!("Chain state verification failed: {}", e);
+            }
+            shutdown.sleep(self.interval).await;
+        }
+    }
+
+    pub fn stats(&self) -> VerifierStats {
+        let last_run_at_ms = self.metrics.last_run_at_ms.load(Ordering::Relaxed);
+        VerifierStats {
+            runs: self.metrics.runs.load(Ordering::Relaxed),
+            banks_checked: self.metrics.banks_checked.load(Ordering::Relaxed),
+            mismatches_total: self.metrics.mismatches_total.load(Ordering::Relaxed),
+            last_run_mismatched: self.metrics.last_run_mismatched.load(Ordering::Relaxed),
+            last_run_at_ms: (last_run_at_ms > 0).then_some(last_run_at_ms),
+        }
+    }
+
+    fn compare(indexed: &IndexedBuckyBankState, on_chain: &OnChainBuckyBank) -> Vec<FieldMismatch> {
+        let mut mismatches = Vec::new();
+
+        if indexed.current_balance as u64 != on_chain.current_balance {
+            mismatches.push(FieldMismatch {
+                field: "current_balance",
+                indexed: serde_json::json!(indexed.current_balance),
+                on_chain: serde_json::json!(on_chain.current_balance),
+            });
+        }
+        if indexed.status != on_chain.status {
+            mismatches.push(FieldMismatch {
+                field: "status",
+                indexed: serde_json::json!(indexed.status),
+                on_chain: serde_json::json!(on_chain.status),
+            });
+        }
+        if indexed.deposit_count as u64 != on_chain.deposit_count {
+            mismatches.push(FieldMismatch {
+                field: "deposit_count",
+                indexed: serde_json::json!(indexed.deposit_count),
+                on_chain: serde_json::json!(on_chain.deposit_count),
+            });
+        }
+        if indexed.last_deposit_ms as u64 != on_chain.last_deposit_ms {
+            mismatches.push(FieldMismatch {
+                field: "last_deposit_ms",
+                indexed: serde_json::json!(indexed.last_deposit_ms),
+                on_chain: serde_json::json!(on_chain.last_deposit_ms),
+            });
+        }
+
+        mismatches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const BANK_ID: &str = "0xd7dd42e481af321a09533e727dd53c0ee58ef2257333e3471358254363c3c3de";
+
+    /// 桩 RPC：返回预设的链上对象
+    #[derive(Default)]
+    struct StubReader {
+        banks: HashMap<String, OnChainBuckyBank>,
+    }
+
+    impl BuckyBankReader for StubReader {
+        async fn get_bucky_bank(&self, bucky_bank_id: &str) -> Result<Option<OnChainBuckyBank>> {
+            Ok(self.banks.get(bucky_bank_id).cloned())
+        }
+    }
+
+    /// 桩索引数据
+    #[derive(Default)]
+    struct StubIndexed {
+        banks: Vec<IndexedBuckyBankState>,
+    }
+
+    impl IndexedStateReader for StubIndexed {
+        async fn get_bucky_bank_ids(&self) -> Result<Vec<String>> {
+            Ok(self.banks.iter().map(|bank| bank.bucky_bank_id.clone()).collect())
+        }
+
+        async fn get_indexed_bucky_bank_state(
+            &self,
+            bucky_bank_id: &str,
+        ) -> Result<Option<IndexedBuckyBankState>> {
+            Ok(self
+                .banks
+                .iter()
+                .find(|bank| bank.bucky_bank_id == bucky_bank_id)
+                .cloned())
+        }
+    }
+
+    fn indexed(current_balance: i64, status: &str) -> IndexedBuckyBankState {
+        IndexedBuckyBankState {
+            bucky_bank_id: BANK_ID.to_string(),
+            current_balance,
+            status: status.to_string(),
+            deposit_count: 2,
+            last_deposit_ms: 1760166241636,
+        }
+    }
+
+    fn on_chain(current_balance: u64, status: &str) -> OnChainBuckyBank {
+        OnChainBuckyBank {
+            current_balance,
+            status: status.to_string(),
+            deposit_count: 2,
+            last_deposit_ms: 1760166241636,
+        }
+    }
+
+    fn verifier(
+        indexed: Vec<IndexedBuckyBankState>,
+        on_chain: Vec<OnChainBuckyBank>,
+    ) -> StateVerifier<StubReader, StubIndexed> {
+        let reader = StubReader {
+            banks: on_chain
+                .into_iter()
+                .map(|bank| (BANK_ID.to_string(), bank))
+                .collect(),
+        };
+        StateVerifier::new(Arc::new(reader), Arc::new(StubIndexed { banks: indexed }), 0)
+    }
+
+    #[test]
+    fn parses_on_chain_object_fields() {
+        let fields = serde_json::json!({
+            "current_balance": "5000000",
+            "status": {"variant": "Active", "fields": {}},
+            "deposit_count": "2",
+            "last_deposit_ms": "1760166241636",
+        });
+
+        let bank = OnChainBuckyBank::from_json(&fields).unwrap();
+        assert_eq!(bank.current_balance, 5000000);
+        assert_eq!(bank.status, "Active");
+        assert_eq!(bank.deposit_count, 2);
+        assert_eq!(bank.last_deposit_ms, 1760166241636);
+
+        assert!(OnChainBuckyBank::from_json(&serde_json::json!({"current_balance": 1})).is_err());
+    }
+
+    #[tokio::test]
+    async fn matching_state_reports_no_mismatch() {
+        let verifier = verifier(vec![indexed(5000000, "Active")], vec![on_chain(5000000, "Active")]);
+
+        let result = verifier.verify(BANK_ID).await.unwrap();
+        assert!(result.matched);
+        assert!(result.mismatches.is_empty());
+
+        assert_eq!(verifier.verify_all().await.unwrap(), 0);
+        let stats = verifier.stats();
+        assert_eq!(stats.runs, 1);
+        assert_eq!(stats.banks_checked, 2);
+        assert_eq!(stats.mismatches_total, 0);
+        assert_eq!(stats.last_run_mismatched, 0);
+        assert!(stats.last_run_at_ms.is_some());
+    }
+
+    #[tokio::test]
+    async fn drifted_state_reports_each_mismatched_field() {
+        let verifier = verifier(vec![indexed(8000000, "Active")], vec![on_chain(5000000, "Completed")]);
+
+        let result = verifier.verify(BANK_ID).await.unwrap();
+        assert!(!result.matched);
+        let fields: Vec<_> = result.mismatches.iter().map(|m| m.field).collect();
+        assert_eq!(fields, ["current_balance", "status"]);
+        assert_eq!(result.mismatches[0].indexed, serde_json::json!(8000000));
+        assert_eq!(result.mismatches[0].on_chain, serde_json::json!(5000000));
+
+        assert_eq!(verifier.verify_all().await.unwrap(), 1);
+        let stats = verifier.stats();
+        assert_eq!(stats.mismatches_total, 2);
+        assert_eq!(stats.last_run_mismatched, 1);
+    }
+
+    #[tokio::test]
+    async fn missing_on_chain_object_is_a_mismatch() {
+        let verifier = verifier(vec![indexed(5000000, "Active")], vec![]);
+
+        let result = verifier.verify(BANK_ID).await.unwrap();
+        assert!(!result.matched);
+        assert_eq!(result.mismatches[0].field, "object");
+        assert_eq!(result.mismatches[0].indexed, serde_json::json!(true));
+        assert_eq!(result.mismatches[0].on_chain, serde_json::json!(false));
+    }
+}
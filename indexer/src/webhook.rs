@@ -0,0 +1,304 @@
+use crate::config::WebhookConfig;
+use crate::database::{Database, models::PendingWebhookDelivery};
+use crate::indexer::{EventType, TARGET_REACHED_EVENT};
+use crate::shutdown::Shutdown;
+use anyhow::Result;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+/// 可以订阅的 webhook 事件类型，订阅时不指定表示全部
+///
+/// 与生成投递记录时使用的事件类型名称一致，即链上事件结构体名称。
+pub const WEBHOOK_EVENT_TYPES: &[&str] = &[
+    EventType::WithdrawalRequested.name(),
+    EventType::WithdrawalApproved.name(),
+    EventType::WithdrawalRejected.name(),
+    EventType::EventWithdrawed.name(),
+    TARGET_REACHED_EVENT,
+];
+
+/// 投递记录状态
+pub const DELIVERY_STATUSES: &[&str] = &["Pending", "Delivered", "Failed"];
+
+// 每轮最多领取的投递记录数
+const CLAIM_BATCH_SIZE: i64 = 50;
+
+// 首次重试等待时间(秒)，之后每次翻倍
+const RETRY_BASE_SECONDS: i64 = 10;
+
+// 重试等待时间上限(秒)
+const RETRY_MAX_SECONDS: i64 = 3600;
+
+/// 计算 webhook 签名：hex(HMAC-SHA256(secret, "{timestamp}.{body}"))
+///
+/// 接收方用同样的方式计算并与 `X-BuckyBank-Signature` 头中 `sha256=` 之后的部分比较，
+/// 同时检查 `X-BuckyBank-Timestamp` 防止重放。
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// 第 attempts 次投递失败后的重试等待时间：10s, 20s, 40s ... 最长 1h
+fn retry_delay_seconds(attempts: i32) -> i64 {
+    let exponent = attempts.saturating_sub(1).clamp(0, 20) as u32;
+    RETRY_BASE_SECONDS
+        .saturating_mul(1 << exponent)
+        .min(RETRY_MAX_SECONDS)
+}
+
+/// 轮询 webhook_deliveries 并投递到订阅地址，失败后按指数退避重试
+pub struct WebhookDispatcher {
+    db: Arc<Database>,
+    client: reqwest::Client,
+    max_attempts: i32,
+    poll_interval: Duration,
+    timeout_seconds: u64,
+}
+
+impl WebhookDispatcher {
+    pub fn new(db: Arc<Database>, config: &WebhookConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_seconds))
+            .build()?;
+
+        Ok(Self {
+            db,
+            client,
+            max_attempts: config.max_attempts.max(1),
+            poll_interval: Duration::from_secs(config.poll_interval_seconds.max(1)),
+            timeout_seconds: config.timeout_seconds,
+        })
+    }
+
+    /// 持续投递直到收到关闭信号，正在投递的一批会完成后再退出
+    pub async fn run(&self, shutdown: Shutdown) {
+        info!(
+            "Starting webhook dispatcher with interval {:?}, max attempts {}",
+            self.poll_interval, self.max_attempts
+        );
+
+        while !shutdown.is_requested() {
+            match self.dispatch_due().await {
+                // 领满一批说明可能还有积压，立即继续
+                Ok(count) if count as i64 >= CLAIM_BATCH_SIZE => continue,
+                Ok(_) => {}
+                Err(e) => error!("Webhook dispatch failed: {}", e),
+            }
+            shutdown.sleep(self.poll_interval).await;
+        }
+        info!("Webhook dispatcher stopped");
+    }
+
+    /// 投递所有到期的记录，返回本轮处理的数量
+    pub async fn dispatch_due(&self) -> Result<usize> {
+        // 领取时按请求超时时间续租，投递过程中进程退出时记录会在租期过后被重新领取
+        let lease_seconds = self.timeout_seconds as i64 + 30;
+        let deliveries = self
+            .db
+            .claim_webhook_deliveries(CLAIM_BATCH_SIZE, lease_seconds)
+            .await?;
+
+        for delivery in &deliveries {
+            self.deliver(delivery).await?;
+        }
+
+        Ok(deliveries.len())
+    }
+
+    async fn deliver(&self, delivery: &PendingWebhookDelivery) -> Result<()> {
+        let (status_code, error) = match send_delivery(&self.client, delivery).await {
+            Ok(status_code) => {
+                debug!("Delivered webhook {} to {}", delivery.id, delivery.url);
+                self.db
+                    .record_webhook_attempt(delivery.id, true, Some(status_code), None, None)
+                    .await?;
+                return Ok(());
+            }
+            Err(failure) => failure,
+        };
+
+        let attempts = delivery.attempts + 1;
+        let retry_after = (attempts < self.max_attempts).then(|| retry_delay_seconds(attempts));
+        match retry_after {
+            Some(seconds) => warn!(
+                "Webhook {} to {} failed (attempt {}): {}, retrying in {}s",
+                delivery.id, delivery.url, attempts, error, seconds
+            ),
+            None => error!(
+                "Webhook {} to {} failed after {} attempts: {}",
+                delivery.id, delivery.url, attempts, error
+            ),
+        }
+
+        self.db
+            .record_webhook_attempt(delivery.id, false, status_code, Some(&error), retry_after)
+            .await
+    }
+}
+
+/// 签名并发送一次投递，成功返回响应状态码，失败返回状态码（如有）和错误信息
+async fn send_delivery(
+    client: &reqwest::Client,
+    delivery: &PendingWebhookDelivery,
+) -> std::result::Result<i32, (Option<i32>, String)> {
+    let body = serde_json::to_string(&delivery.payload).map_err(|e| (None, e.to_string()))?;
+    let timestamp = chrono::Utc::now().timestamp();
+    let signature = sign_payload(&delivery.secret, timestamp, &body);
+
+    let response = client
+        .post(&delivery.url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header("X-BuckyBank-Event", &delivery.event_type)
+        .header("X-BuckyBank-Delivery", delivery.id.to_string())
+        .header("X-BuckyBank-Timestamp", timestamp.to_string())
+        .header("X-BuckyBank-Signature", format!("sha256={}", signature))
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| (None, e.to_string()))?;
+
+    let status = response.status();
+    if status.is_success() {
+        Ok(status.as_u16() as i32)
+    } else {
+        Err((Some(status.as_u16() as i32), format!("HTTP {}", status)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::body::Bytes;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    // 本地 webhook 接收端：记录收到的请求头和请求体，并返回固定的状态码
+    async fn spawn_receiver(
+        status: StatusCode,
+    ) -> (String, mpsc::UnboundedReceiver<(HeaderMap, String)>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let app = Router::new().route(
+            "/hook",
+            post(move |headers: HeaderMap, body: Bytes| {
+                let sender = sender.clone();
+                async move {
+                    let _ = sender.send((headers, String::from_utf8_lossy(&body).into_owned()));
+                    status
+                }
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}/hook", address), receiver)
+    }
+
+    fn delivery(url: &str) -> PendingWebhookDelivery {
+        PendingWebhookDelivery {
+            id: uuid::Uuid::new_v4(),
+            event_type: EventType::WithdrawalRequested.name().to_string(),
+            payload: serde_json::json!({
+                "event_type": "EventWithdrawalRequested",
+                "bucky_bank_id": "0xd7dd42e481af321a09533e727dd53c0ee58ef2257333e3471358254363c3c3de",
+            }),
+            attempts: 0,
+            url: url.to_string(),
+            secret: "whsec_test".to_string(),
+        }
+    }
+
+    fn header<'a>(headers: &'a HeaderMap, name: &str) -> &'a str {
+        headers.get(name).unwrap().to_str().unwrap()
+    }
+
+    #[tokio::test]
+    async fn delivers_signed_payload() {
+        let (url, mut receiver) = spawn_receiver(StatusCode::NO_CONTENT).await;
+        let delivery = delivery(&url);
+
+        let status_code = send_delivery(&reqwest::Client::new(), &delivery)
+            .await
+            .unwrap();
+        assert_eq!(status_code, 204);
+
+        let (headers, body) = receiver.recv().await.unwrap();
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&body).unwrap(),
+            delivery.payload
+        );
+        assert_eq!(
+            header(&headers, "X-BuckyBank-Event"),
+            "EventWithdrawalRequested"
+        );
+        assert_eq!(
+            header(&headers, "X-BuckyBank-Delivery"),
+            delivery.id.to_string()
+        );
+
+        // 接收方按文档的方式验签
+        let timestamp: i64 = header(&headers, "X-BuckyBank-Timestamp").parse().unwrap();
+        let expected = format!(
+            "sha256={}",
+            sign_payload(&delivery.secret, timestamp, &body)
+        );
+        assert_eq!(header(&headers, "X-BuckyBank-Signature"), expected);
+    }
+
+    #[tokio::test]
+    async fn reports_status_of_rejected_delivery() {
+        let (url, _receiver) = spawn_receiver(StatusCode::INTERNAL_SERVER_ERROR).await;
+
+        let (status_code, error) = send_delivery(&reqwest::Client::new(), &delivery(&url))
+            .await
+            .unwrap_err();
+        assert_eq!(status_code, Some(500));
+        assert!(error.contains("500"), "{}", error);
+    }
+
+    #[tokio::test]
+    async fn reports_unreachable_receiver() {
+        // 绑定后立即释放端口，连接会被拒绝
+        let address = TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+            .local_addr()
+            .unwrap();
+
+        let (status_code, _) = send_delivery(
+            &reqwest::Client::new(),
+            &delivery(&format!("http://{}/hook", address)),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(status_code, None);
+    }
+
+    #[test]
+    fn subscribable_types_match_enqueued_event_names() {
+        for event_type in WEBHOOK_EVENT_TYPES {
+            assert!(
+                *event_type == TARGET_REACHED_EVENT || EventType::from_name(event_type).is_some(),
+                "{} is not an indexed event name",
+                event_type
+            );
+        }
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap() {
+        assert_eq!(retry_delay_seconds(1), 10);
+        assert_eq!(retry_delay_seconds(2), 20);
+        assert_eq!(retry_delay_seconds(4), 80);
+        assert_eq!(retry_delay_seconds(30), RETRY_MAX_SECONDS);
+    }
+}
@@ -0,0 +1,97 @@
+-- 新增 EventBankReward / EventClaimReward 事件表
+
+-- 继承之前的 UUID 扩展（如果已经存在则跳过）
+CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
+
+-- EventBankReward 事件表（split_reward 按存款比例分配奖励）
+CREATE TABLE IF NOT EXISTS bank_reward_events (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    bucky_bank_id VARCHAR(66) NOT NULL,
+    banks_count BIGINT NOT NULL,
+    reward_receiver VARCHAR(66) NOT NULL,
+    total_reward BIGINT NOT NULL,
+    bank_reward BIGINT NOT NULL,
+    total_balance BIGINT NOT NULL,
+    bank_balance BIGINT NOT NULL,
+    tx_digest VARCHAR(255) NOT NULL,
+    event_seq BIGINT NOT NULL,
+    timestamp_ms BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    -- 外键约束，确保bucky_bank_id引用已存在的BuckyBank
+    FOREIGN KEY (bucky_bank_id) REFERENCES bucky_bank_created_events(bucky_bank_id)
+);
+
+-- EventClaimReward 事件表（claim_reward 领取奖励）
+CREATE TABLE IF NOT EXISTS reward_claim_events (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    bucky_bank_id VARCHAR(66) NOT NULL,
+    reward_receiver VARCHAR(66) NOT NULL,
+    reward_amount BIGINT NOT NULL,
+    created_at_ms BIGINT NOT NULL,
+    tx_digest VARCHAR(255) NOT NULL,
+    event_seq BIGINT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    -- 外键约束，确保bucky_bank_id引用已存在的BuckyBank
+    FOREIGN KEY (bucky_bank_id) REFERENCES bucky_bank_created_events(bucky_bank_id)
+);
+
+-- 添加索引以提高查询性能
+CREATE INDEX idx_bank_reward_bucky_bank_id ON bank_reward_events (bucky_bank_id);
+CREATE INDEX idx_bank_reward_receiver ON bank_reward_events (reward_receiver);
+CREATE INDEX idx_bank_reward_timestamp ON bank_reward_events (timestamp_ms);
+
+CREATE INDEX idx_reward_claim_bucky_bank_id ON reward_claim_events (bucky_bank_id);
+CREATE INDEX idx_reward_claim_receiver ON reward_claim_events (reward_receiver);
+CREATE INDEX idx_reward_claim_timestamp ON reward_claim_events (created_at_ms);
+
+-- 复合索引，用于常见查询场景
+CREATE INDEX idx_bank_reward_bank_receiver ON bank_reward_events (bucky_bank_id, reward_receiver);
+CREATE INDEX idx_reward_claim_bank_receiver ON reward_claim_events (bucky_bank_id, reward_receiver);
+
+-- 添加注释
+COMMENT ON TABLE bank_reward_events IS 'BuckyBank奖励分配事件记录表';
+
+COMMENT ON COLUMN bank_reward_events.bucky_bank_id IS '关联的BuckyBank标识符';
+COMMENT ON COLUMN bank_reward_events.banks_count IS '分配时奖励接收者的存钱罐数量';
+COMMENT ON COLUMN bank_reward_events.reward_receiver IS '奖励接收者地址';
+COMMENT ON COLUMN bank_reward_events.total_reward IS '本次分配的总奖励(SUI)';
+COMMENT ON COLUMN bank_reward_events.bank_reward IS '该存钱罐分得的奖励(SUI)';
+COMMENT ON COLUMN bank_reward_events.total_balance IS '奖励接收者总存款余额(USDC)';
+COMMENT ON COLUMN bank_reward_events.bank_balance IS '该存钱罐存款余额(USDC)';
+COMMENT ON COLUMN bank_reward_events.tx_digest IS '交易摘要哈希';
+COMMENT ON COLUMN bank_reward_events.event_seq IS '事件序列号';
+COMMENT ON COLUMN bank_reward_events.timestamp_ms IS '链上事件时间戳(毫秒)';
+
+COMMENT ON TABLE reward_claim_events IS 'BuckyBank奖励领取事件记录表';
+
+COMMENT ON COLUMN reward_claim_events.bucky_bank_id IS '关联的BuckyBank标识符';
+COMMENT ON COLUMN reward_claim_events.reward_receiver IS '奖励领取者地址';
+COMMENT ON COLUMN reward_claim_events.reward_amount IS '领取的奖励金额(SUI)';
+COMMENT ON COLUMN reward_claim_events.created_at_ms IS '领取时间戳(毫秒)';
+COMMENT ON COLUMN reward_claim_events.tx_digest IS '交易摘要哈希';
+COMMENT ON COLUMN reward_claim_events.event_seq IS '事件序列号';
+
+-- 可选：添加一个视图来方便查询奖励统计
+CREATE OR REPLACE VIEW reward_summary AS
+SELECT
+    b.bucky_bank_id,
+    b.name as bucky_bank_name,
+    COALESCE(r.total_rewarded, 0) as total_rewarded,
+    COALESCE(c.total_claimed, 0) as total_claimed,
+    COALESCE(r.total_rewarded, 0) - COALESCE(c.total_claimed, 0) as unclaimed_reward
+FROM
+    bucky_bank_created_events b
+    LEFT JOIN (
+        SELECT bucky_bank_id, SUM(bank_reward) as total_rewarded
+        FROM bank_reward_events
+        GROUP BY bucky_bank_id
+    ) r ON r.bucky_bank_id = b.bucky_bank_id
+    LEFT JOIN (
+        SELECT bucky_bank_id, SUM(reward_amount) as total_claimed
+        FROM reward_claim_events
+        GROUP BY bucky_bank_id
+    ) c ON c.bucky_bank_id = b.bucky_bank_id;
+
+COMMENT ON VIEW reward_summary IS 'BuckyBank奖励统计视图';
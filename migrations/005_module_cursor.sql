@@ -0,0 +1,25 @@
+-- 事件索引改为按模块单一事件流处理，游标由 package::module::event_name 改为 package::module
+
+-- 用旧游标中最早的一个初始化模块游标，升级后从该位置继续，不从头重新扫描。
+-- 旧游标只记录了 tx_digest 和 event_seq，按 updated_at 近似链上顺序：
+-- 各事件类型在每轮轮询中都会追到最新，最后更新越早的游标位置越靠前。
+-- 更靠后的其他类型事件会被重新处理，由事件表的唯一约束去重。
+-- 之前没有游标的事件类型（如新增的奖励事件）在该位置之前的历史事件需通过 backfill 子命令补齐。
+INSERT INTO cursors (id, tx_digest, event_seq)
+SELECT DISTINCT ON (module_id) module_id, tx_digest, event_seq
+FROM (
+    SELECT
+        split_part(id, '::', 1) || '::' || split_part(id, '::', 2) AS module_id,
+        tx_digest,
+        event_seq,
+        updated_at
+    FROM cursors
+    WHERE id LIKE '%::%::%'
+) old_cursors
+ORDER BY module_id, updated_at ASC
+ON CONFLICT (id) DO NOTHING;
+
+-- 清理旧的按事件类型划分的游标
+DELETE FROM cursors WHERE id LIKE '%::%::%';
+
+COMMENT ON COLUMN cursors.id IS '事件流标识符，格式: package::module';
@@ -0,0 +1,52 @@
+-- 为所有事件表补充链上事件ID (tx_digest, event_seq)，用于保证事件只被写入一次
+
+-- 历史数据没有事件ID，因此新增列允许为空；PostgreSQL 中 NULL 不参与唯一约束冲突
+ALTER TABLE bucky_bank_created_events
+    ADD COLUMN IF NOT EXISTS tx_digest VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS event_seq BIGINT;
+
+ALTER TABLE deposit_made_events
+    ADD COLUMN IF NOT EXISTS tx_digest VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS event_seq BIGINT;
+
+ALTER TABLE withdrawal_requests
+    ADD COLUMN IF NOT EXISTS tx_digest VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS event_seq BIGINT,
+    ADD COLUMN IF NOT EXISTS timestamp_ms BIGINT;
+
+ALTER TABLE withdrawed_events
+    ADD COLUMN IF NOT EXISTS tx_digest VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS event_seq BIGINT;
+
+-- 唯一约束，同一个链上事件重复写入时通过 ON CONFLICT 跳过
+ALTER TABLE bucky_bank_created_events
+    ADD CONSTRAINT uq_bucky_bank_created_event_id UNIQUE (tx_digest, event_seq);
+
+ALTER TABLE deposit_made_events
+    ADD CONSTRAINT uq_deposit_made_event_id UNIQUE (tx_digest, event_seq);
+
+ALTER TABLE withdrawal_requests
+    ADD CONSTRAINT uq_withdrawal_request_event_id UNIQUE (tx_digest, event_seq);
+
+ALTER TABLE withdrawed_events
+    ADD CONSTRAINT uq_withdrawed_event_id UNIQUE (tx_digest, event_seq);
+
+ALTER TABLE bank_reward_events
+    ADD CONSTRAINT uq_bank_reward_event_id UNIQUE (tx_digest, event_seq);
+
+ALTER TABLE reward_claim_events
+    ADD CONSTRAINT uq_reward_claim_event_id UNIQUE (tx_digest, event_seq);
+
+-- 添加注释
+COMMENT ON COLUMN bucky_bank_created_events.tx_digest IS '交易摘要哈希';
+COMMENT ON COLUMN bucky_bank_created_events.event_seq IS '事件序列号';
+
+COMMENT ON COLUMN deposit_made_events.tx_digest IS '交易摘要哈希';
+COMMENT ON COLUMN deposit_made_events.event_seq IS '事件序列号';
+
+COMMENT ON COLUMN withdrawal_requests.tx_digest IS '交易摘要哈希';
+COMMENT ON COLUMN withdrawal_requests.event_seq IS '事件序列号';
+COMMENT ON COLUMN withdrawal_requests.timestamp_ms IS '链上事件时间戳(毫秒)';
+
+COMMENT ON COLUMN withdrawed_events.tx_digest IS '交易摘要哈希';
+COMMENT ON COLUMN withdrawed_events.event_seq IS '事件序列号';
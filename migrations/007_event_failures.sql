@@ -0,0 +1,35 @@
+-- 新增事件处理失败（死信）表
+
+-- 继承之前的 UUID 扩展（如果已经存在则跳过）
+CREATE EXTENSION IF NOT EXISTS "uuid-ossp";
+
+-- 多次重试仍处理失败的事件，按配置写入此表后游标继续前移
+CREATE TABLE IF NOT EXISTS event_failures (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    tx_digest VARCHAR(255) NOT NULL,
+    event_seq BIGINT NOT NULL,
+    event_type VARCHAR(255) NOT NULL,
+    parsed_json JSONB NOT NULL,
+    error TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    timestamp_ms BIGINT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE (tx_digest, event_seq)
+);
+
+-- 添加索引以提高查询性能
+CREATE INDEX idx_event_failures_event_type ON event_failures (event_type);
+CREATE INDEX idx_event_failures_created_at ON event_failures (created_at);
+
+-- 添加注释
+COMMENT ON TABLE event_failures IS '事件处理失败记录表（死信表）';
+
+COMMENT ON COLUMN event_failures.tx_digest IS '交易摘要哈希';
+COMMENT ON COLUMN event_failures.event_seq IS '事件序列号';
+COMMENT ON COLUMN event_failures.event_type IS '事件类型名称';
+COMMENT ON COLUMN event_failures.parsed_json IS '链上事件原始 parsed_json';
+COMMENT ON COLUMN event_failures.error IS '最近一次处理失败的错误信息';
+COMMENT ON COLUMN event_failures.attempts IS '累计处理次数';
+COMMENT ON COLUMN event_failures.timestamp_ms IS '链上事件时间戳(毫秒)';
@@ -0,0 +1,11 @@
+-- 死信事件重放：记录重放成功的时间，保留失败记录用于审计
+
+ALTER TABLE event_failures
+    ADD COLUMN IF NOT EXISTS resolved_at TIMESTAMP WITH TIME ZONE;
+
+-- 重放时按写入顺序查询未处理的失败事件
+CREATE INDEX idx_event_failures_unresolved ON event_failures (created_at)
+WHERE
+    resolved_at IS NULL;
+
+COMMENT ON COLUMN event_failures.resolved_at IS '重放成功时间，为空表示尚未处理';
@@ -0,0 +1,44 @@
+-- 存钱罐生命周期状态，与合约中的 Status 枚举保持一致
+
+ALTER TABLE bucky_bank_created_events
+    ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'Active'
+    CHECK (
+        status IN (
+            'Active',
+            'Frozen',
+            'Completed',
+            'Closed'
+        )
+    );
+
+-- 历史数据：存款后余额达到目标金额的存钱罐为 Completed（合约中不会再变回 Active）
+WITH ledger AS (
+    SELECT bucky_bank_id, created_at_ms, amount AS delta, TRUE AS is_deposit
+    FROM deposit_made_events
+    UNION ALL
+    SELECT bucky_bank_id, created_at_ms, - amount AS delta, FALSE AS is_deposit
+    FROM withdrawed_events
+),
+running AS (
+    SELECT
+        bucky_bank_id,
+        is_deposit,
+        SUM(delta) OVER (
+            PARTITION BY bucky_bank_id
+            ORDER BY created_at_ms, is_deposit DESC
+            ROWS UNBOUNDED PRECEDING
+        ) AS balance
+    FROM ledger
+)
+UPDATE bucky_bank_created_events b
+SET status = 'Completed'
+WHERE EXISTS (
+    SELECT 1 FROM running r
+    WHERE r.bucky_bank_id = b.bucky_bank_id
+      AND r.is_deposit
+      AND r.balance >= b.target_amount
+);
+
+CREATE INDEX idx_bucky_bank_created_status ON bucky_bank_created_events (status);
+
+COMMENT ON COLUMN bucky_bank_created_events.status IS '存钱罐状态：Active, Frozen, Completed, Closed';
@@ -0,0 +1,20 @@
+-- 列表接口按 (时间, id) 倒序做游标分页，索引覆盖排序键，btree 可反向扫描
+
+CREATE INDEX IF NOT EXISTS idx_bucky_bank_created_keyset ON bucky_bank_created_events (created_at_ms, id);
+
+CREATE INDEX IF NOT EXISTS idx_deposit_made_bank_keyset ON deposit_made_events (bucky_bank_id, created_at_ms, id);
+
+CREATE INDEX IF NOT EXISTS idx_event_withdrawed_bank_keyset ON withdrawed_events (bucky_bank_id, created_at_ms, id);
+
+CREATE INDEX IF NOT EXISTS idx_withdrawal_bank_keyset ON withdrawal_requests (bucky_bank_id, created_at_ms, id);
+
+CREATE INDEX IF NOT EXISTS idx_withdrawal_requester_keyset ON withdrawal_requests (requester, created_at_ms, id);
+
+CREATE INDEX IF NOT EXISTS idx_bank_reward_keyset ON bank_reward_events (timestamp_ms, id);
+
+CREATE INDEX IF NOT EXISTS idx_reward_claim_keyset ON reward_claim_events (created_at_ms, id);
+
+-- 以下索引是新索引的前缀，不再需要
+DROP INDEX IF EXISTS idx_deposit_made_bank_timestamp;
+
+DROP INDEX IF EXISTS idx_event_withdrawed_bank_timestamp;
@@ -0,0 +1,52 @@
+-- 平台统计，对应合约 GlobalStats 并补充金额、状态分布和奖励等汇总
+-- 由索引器在写入新事件后执行 REFRESH MATERIALIZED VIEW CONCURRENTLY 刷新
+
+CREATE MATERIALIZED VIEW IF NOT EXISTS platform_stats AS
+SELECT
+    1 AS id,
+    banks.total_bucky_banks,
+    banks.active_bucky_banks,
+    banks.completed_bucky_banks,
+    banks.total_balance,
+    deposits.total_deposits,
+    deposits.total_deposit_amount,
+    deposits.unique_depositors,
+    withdrawals.total_withdrawals,
+    withdrawals.total_withdrawal_amount,
+    rewards.total_rewards_distributed,
+    claims.total_rewards_claimed,
+    NOW() AS refreshed_at
+FROM (
+    SELECT
+        COUNT(*) AS total_bucky_banks,
+        COUNT(*) FILTER (WHERE status = 'Active') AS active_bucky_banks,
+        COUNT(*) FILTER (WHERE status = 'Completed') AS completed_bucky_banks,
+        COALESCE(SUM(current_balance), 0)::BIGINT AS total_balance
+    FROM bucky_bank_created_events
+) banks
+CROSS JOIN (
+    SELECT
+        COUNT(*) AS total_deposits,
+        COALESCE(SUM(amount), 0)::BIGINT AS total_deposit_amount,
+        COUNT(DISTINCT depositor) AS unique_depositors
+    FROM deposit_made_events
+) deposits
+CROSS JOIN (
+    SELECT
+        COUNT(*) AS total_withdrawals,
+        COALESCE(SUM(amount), 0)::BIGINT AS total_withdrawal_amount
+    FROM withdrawed_events
+) withdrawals
+CROSS JOIN (
+    SELECT COALESCE(SUM(bank_reward), 0)::BIGINT AS total_rewards_distributed
+    FROM bank_reward_events
+) rewards
+CROSS JOIN (
+    SELECT COALESCE(SUM(reward_amount), 0)::BIGINT AS total_rewards_claimed
+    FROM reward_claim_events
+) claims;
+
+-- CONCURRENTLY 刷新要求唯一索引
+CREATE UNIQUE INDEX IF NOT EXISTS idx_platform_stats_id ON platform_stats (id);
+
+COMMENT ON MATERIALIZED VIEW platform_stats IS '平台统计，由索引器在写入新事件后刷新';
@@ -0,0 +1,30 @@
+-- 管理接口 API Key 与提取请求状态人工修正记录
+-- 替换原来无认证的 PUT /api/withdrawal-requests/:request_id/status，所有人工修正都需要 API Key 并留下审计记录
+
+CREATE TABLE IF NOT EXISTS admin_api_keys (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    -- 使用者名称，写入审计记录
+    name VARCHAR(100) NOT NULL UNIQUE,
+    -- API Key 的 SHA-256 摘要（hex），明文只在创建时输出一次
+    key_hash CHAR(64) NOT NULL UNIQUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    last_used_at TIMESTAMP WITH TIME ZONE,
+    revoked_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE TABLE IF NOT EXISTS withdrawal_status_corrections (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    request_id VARCHAR(66) NOT NULL REFERENCES withdrawal_requests (request_id),
+    -- 修正前由链上事件得到的状态
+    previous_status VARCHAR(20) NOT NULL,
+    corrected_status VARCHAR(20) NOT NULL,
+    reason TEXT NOT NULL,
+    -- 执行修正的 API Key 名称
+    corrected_by VARCHAR(100) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_withdrawal_status_corrections_request
+    ON withdrawal_status_corrections (request_id, created_at DESC);
+
+COMMENT ON TABLE withdrawal_status_corrections IS '提取请求状态人工修正审计记录，只追加不修改';
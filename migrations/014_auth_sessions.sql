@@ -0,0 +1,24 @@
+-- Sui 签名登录：一次性挑战与短期访问令牌
+-- 客户端用 Sui 私钥签名挑战消息，服务端验签后签发令牌，持令牌可以查看自己存钱罐的私密字段（如提取原因）
+
+CREATE TABLE IF NOT EXISTS auth_challenges (
+    nonce UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    address VARCHAR(66) NOT NULL,
+    -- 需要签名的完整消息，验签时按原文校验
+    message TEXT NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    -- 挑战只能使用一次
+    used_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS auth_sessions (
+    -- 令牌的 SHA-256 摘要（hex），明文只在签发时返回
+    token_hash CHAR(64) PRIMARY KEY,
+    address VARCHAR(66) NOT NULL,
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_auth_challenges_expires_at ON auth_challenges (expires_at);
+CREATE INDEX IF NOT EXISTS idx_auth_sessions_expires_at ON auth_sessions (expires_at);
@@ -0,0 +1,11 @@
+-- webhook 订阅的事件类型改为与投递记录一致的链上事件结构体名称
+UPDATE webhook_subscriptions
+SET event_types = array_replace(
+        array_replace(
+            array_replace(event_types, 'WithdrawalRequested', 'EventWithdrawalRequested'),
+            'WithdrawalApproved', 'EventWithdrawalApproved'
+        ),
+        'WithdrawalRejected', 'EventWithdrawalRejected'
+    ),
+    updated_at = CURRENT_TIMESTAMP
+WHERE event_types && ARRAY['WithdrawalRequested', 'WithdrawalApproved', 'WithdrawalRejected'];
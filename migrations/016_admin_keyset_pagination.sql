@@ -0,0 +1,29 @@
+-- 管理接口列表（死信事件、Webhook 投递记录、状态修正记录）按 (created_at, id) 倒序做游标分页
+-- 游标比较要求排序键非空，created_at 原本只有默认值，这里补齐后加上非空约束
+
+UPDATE event_failures SET created_at = CURRENT_TIMESTAMP WHERE created_at IS NULL;
+
+UPDATE webhook_deliveries SET created_at = CURRENT_TIMESTAMP WHERE created_at IS NULL;
+
+UPDATE withdrawal_status_corrections SET created_at = CURRENT_TIMESTAMP WHERE created_at IS NULL;
+
+ALTER TABLE event_failures ALTER COLUMN created_at SET NOT NULL;
+
+ALTER TABLE webhook_deliveries ALTER COLUMN created_at SET NOT NULL;
+
+ALTER TABLE withdrawal_status_corrections ALTER COLUMN created_at SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_event_failures_keyset ON event_failures (created_at, id);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_subscription_keyset ON webhook_deliveries (subscription_id, created_at, id);
+
+CREATE INDEX IF NOT EXISTS idx_withdrawal_status_corrections_keyset ON withdrawal_status_corrections (created_at, id);
+
+CREATE INDEX IF NOT EXISTS idx_withdrawal_status_corrections_request_keyset ON withdrawal_status_corrections (request_id, created_at, id);
+
+-- 以下索引是新索引的前缀，不再需要
+DROP INDEX IF EXISTS idx_event_failures_created_at;
+
+DROP INDEX IF EXISTS idx_webhook_deliveries_subscription;
+
+DROP INDEX IF EXISTS idx_withdrawal_status_corrections_request;